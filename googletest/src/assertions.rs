@@ -307,6 +307,56 @@ macro_rules! fail {
 }
 pub use fail;
 
+/// Skips the current test, ending it early.
+///
+/// This is the analogue of `GTEST_SKIP()` in GoogleTest C++. The test is
+/// reported as skipped rather than as passed, with the given reason. This is
+/// useful when the test cannot run in the current environment:
+///
+/// ```ignore
+/// #[gtest]
+/// fn computes_with_avx2() {
+///     if !is_x86_feature_detected!("avx2") {
+///         skip!("AVX2 is not supported on this machine");
+///     }
+///     ...
+/// }
+/// ```
+///
+/// Unlike [`fail!`], this macro returns from the invoking function. That
+/// function must return `()` or a `Result` whose error type can be converted
+/// from [`TestAssertionFailure`][crate::TestAssertionFailure], such as
+/// [`googletest::Result`][crate::Result]. This excludes `anyhow::Result`, as
+/// `anyhow::Error` cannot be converted from it. If the invoking function is
+/// called by the test, the test continues once it returns but is still
+/// reported as skipped.
+///
+/// With `googletest::Result`, a skipped test which then returns another error,
+/// for example from [`verify_that!`], fails. With another error type, the skip
+/// cannot be told apart from other errors, so any error returned by a skipped
+/// test is taken to be the skip.
+///
+/// A test which has already failed is reported as failed, not as skipped.
+///
+/// This can only be invoked inside tests with the
+/// [`gtest`][crate::gtest] attribute.
+#[macro_export]
+macro_rules! skip {
+    ($($message:expr),+ $(,)?) => {
+        return $crate::assertions::internal::skip_test(format!($($message),*))
+    };
+
+    () => { $crate::skip!("Test skipped") };
+}
+pub use skip;
+
+/// Alias for [`skip!`], named after `GTEST_SKIP()` in GoogleTest C++.
+#[macro_export]
+macro_rules! gtest_skip {
+    ($($message:tt)*) => { $crate::skip!($($message)*) };
+}
+pub use gtest_skip;
+
 /// Generates a success. This **does not** make the overall test succeed. A test
 /// is only considered successful if none of its assertions fail during its
 /// execution.
@@ -1873,6 +1923,190 @@ macro_rules! expect_pred {
 }
 pub use expect_pred;
 
+/// Checks that `statement` exits the process with an exit code matched by
+/// `exit_code_matcher` while writing to the standard error what
+/// `stderr_matcher` matches.
+///
+/// This is a *death test*: the statement does not run in the process of the
+/// test. Instead, the test binary is executed again in a child process which
+/// runs only the current test, up to this statement. This makes it possible
+/// to test code which calls [`std::process::exit`] or
+/// [`std::process::abort`], or which panics in a binary built with
+/// `panic = "abort"`, none of which `#[should_panic]` can handle.
+///
+/// Evaluates to `Result::Ok(())` if the child process exits as expected and
+/// `Result::Err(TestAssertionFailure)` otherwise, including when the statement
+/// returns. The exit code matcher matches an `i32` and the standard error
+/// matcher a `&str` holding what the child wrote after starting to run the
+/// statement.
+///
+/// ```ignore
+/// #[gtest]
+/// fn exits_on_invalid_configuration() -> Result<()> {
+///     verify_exit!(load_config("invalid"), eq(2), contains_substring("invalid"))
+/// }
+/// ```
+///
+/// As the child process runs the test from its start until it reaches the
+/// statement, code preceding the statement in the test should not have side
+/// effects outside the process. This can only be invoked inside tests with the
+/// [`gtest`][crate::gtest] attribute, which are run by the test harness of
+/// the test binary.
+#[macro_export]
+macro_rules! verify_exit {
+    ($statement:expr, $exit_code_matcher:expr, $stderr_matcher:expr $(,)?) => {
+        $crate::internal::death_test::run_exit_test(
+            concat!(file!(), ":", line!(), ":", column!()),
+            stringify!($statement),
+            || {
+                $statement;
+            },
+            $exit_code_matcher,
+            $stderr_matcher,
+        )
+    };
+}
+pub use verify_exit;
+
+/// Marks test as failed and continues execution if `statement` does not exit
+/// the process with an exit code matched by `exit_code_matcher` while writing
+/// to the standard error what `stderr_matcher` matches.
+///
+/// This is the non-fatal version of [`verify_exit!`], which describes how the
+/// statement runs in a child process.
+///
+/// ```ignore
+/// #[gtest]
+/// fn exits_on_invalid_configuration() {
+///     expect_exit!(load_config("invalid"), eq(2), contains_substring("invalid"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! expect_exit {
+    ($statement:expr, $exit_code_matcher:expr, $stderr_matcher:expr $(,)?) => {{
+        $crate::GoogleTestSupport::and_log_failure($crate::verify_exit!(
+            $statement,
+            $exit_code_matcher,
+            $stderr_matcher
+        ));
+    }};
+}
+pub use expect_exit;
+
+/// Checks that `statement` kills the process, writing to the standard error
+/// what `stderr_matcher` matches.
+///
+/// The process dies if it exits with a non-zero exit code or is killed by a
+/// signal, for instance through [`std::process::abort`]. Like
+/// [`verify_exit!`], this runs the statement in a child process.
+///
+/// ```ignore
+/// #[gtest]
+/// fn aborts_on_corruption() -> Result<()> {
+///     verify_death!(Index::open("corrupt"), contains_substring("corrupt index"))
+/// }
+/// ```
+#[macro_export]
+macro_rules! verify_death {
+    ($statement:expr, $stderr_matcher:expr $(,)?) => {
+        $crate::internal::death_test::run_death_test_expecting_death(
+            concat!(file!(), ":", line!(), ":", column!()),
+            stringify!($statement),
+            || {
+                $statement;
+            },
+            $stderr_matcher,
+        )
+    };
+}
+pub use verify_death;
+
+/// Marks test as failed and continues execution if `statement` does not kill
+/// the process while writing to the standard error what `stderr_matcher`
+/// matches.
+///
+/// This is the non-fatal version of [`verify_death!`].
+///
+/// ```ignore
+/// #[gtest]
+/// fn aborts_on_corruption() {
+///     expect_death!(Index::open("corrupt"), contains_substring("corrupt index"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! expect_death {
+    ($statement:expr, $stderr_matcher:expr $(,)?) => {{
+        $crate::GoogleTestSupport::and_log_failure($crate::verify_death!(
+            $statement,
+            $stderr_matcher
+        ));
+    }};
+}
+pub use expect_death;
+
+/// Checks that `statement` panics with a message matched by `expected`.
+///
+/// Evaluates to `Result::Ok(())` if `statement` panics with a `&str` or `String`
+/// payload which `expected` matches and `Result::Err(TestAssertionFailure)`
+/// otherwise, in particular if `statement` does not panic. The panic is caught
+/// with [`std::panic::catch_unwind`] and does not fail the test, so that a test
+/// may check several panics, which `#[should_panic]` cannot:
+///
+/// ```
+/// # use googletest::prelude::*;
+/// # fn should_pass() -> Result<()> {
+/// let values: Vec<u32> = vec![];
+/// verify_panics!(values[1], contains_substring("index out of bounds"))?;
+/// verify_panics!(u32::MAX.checked_add(1).expect("overflow"), eq("overflow"))?;
+/// verify_panics!(
+///     panic!("invalid state: {}", 3),
+///     contains_regex("invalid state: [0-9]+")
+/// )?;
+/// #     Ok(())
+/// # }
+/// # should_pass().unwrap();
+/// ```
+///
+/// The message printed by the panic hook still appears in the output of the
+/// test. This does not work when panics abort the process, for instance with
+/// `panic = "abort"`. Use [`verify_death!`] there instead.
+#[macro_export]
+macro_rules! verify_panics {
+    ($statement:expr, $expected:expr $(,)?) => {
+        $crate::assertions::internal::check_panics(
+            || {
+                #[allow(clippy::diverging_sub_expression)]
+                let _ = $statement;
+            },
+            $expected,
+            stringify!($statement),
+            concat!("panic message of ", stringify!($statement)),
+        )
+    };
+}
+pub use verify_panics;
+
+/// Marks test as failed and continues execution if `statement` does not panic
+/// with a message matched by `expected`.
+///
+/// This is the non-fatal version of [`verify_panics!`]. This can only be
+/// invoked inside tests with the [`gtest`][crate::gtest] attribute.
+///
+/// ```ignore
+/// #[gtest]
+/// fn rejects_invalid_input() {
+///     expect_panics!(parse(""), contains_substring("empty input"));
+///     expect_panics!(parse("-"), contains_regex("unexpected .* at 0"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! expect_panics {
+    ($statement:expr, $expected:expr $(,)?) => {{
+        $crate::GoogleTestSupport::and_log_failure($crate::verify_panics!($statement, $expected));
+    }};
+}
+pub use expect_panics;
+
 /// Functions for use only by the procedural macros in this module.
 ///
 /// **For internal use only. API stablility is not guaranteed!**
@@ -1933,13 +2167,79 @@ pub mod internal {
     pub fn create_fail_result(message: String) -> crate::Result<()> {
         Err(crate::internal::test_outcome::TestAssertionFailure::create(message))
     }
+
+    /// Runs `statement`, whose source is `statement_expr`, checking that it
+    /// panics with a message matched by `expected`.
+    ///
+    /// The panic is not a failure of the running test.
+    ///
+    /// **For internal use only. API stability is not guaranteed!**
+    #[must_use = "The assertion result must be evaluated to affect the test result."]
+    #[track_caller]
+    pub fn check_panics(
+        statement: impl FnOnce(),
+        expected: impl for<'a> Matcher<&'a str>,
+        statement_expr: &'static str,
+        message_expr: &'static str,
+    ) -> Result<(), TestAssertionFailure> {
+        let result = crate::internal::test_report::expecting_panic(|| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(statement))
+        });
+        let Err(payload) = result else {
+            return Err(TestAssertionFailure::create(format!(
+                "\
+Value of: {statement_expr}
+Expected: panics with a message which {}
+Actual: does not panic",
+                expected.describe(MatcherResult::Match)
+            )));
+        };
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            return Err(TestAssertionFailure::create(format!(
+                "{statement_expr} panicked with a payload which is neither a &str nor a String"
+            )));
+        };
+        message.as_str().check(expected, message_expr)
+    }
+
+    /// Marks the current test as skipped for the given `reason` and produces
+    /// the value with which `skip!` returns.
+    ///
+    /// **For internal use only. API stability is not guaranteed!**
+    #[track_caller]
+    pub fn skip_test<R: SkipResult>(reason: String) -> R {
+        crate::internal::test_outcome::TestOutcome::skip_current_test(&reason);
+        R::skipped(TestAssertionFailure::skip(&reason))
+    }
+
+    /// A return type of functions in which `skip!` can be invoked.
+    ///
+    /// **For internal use only. API stability is not guaranteed!**
+    pub trait SkipResult {
+        /// Returns the value which ends the function, given the `failure`
+        /// describing the skip.
+        fn skipped(failure: TestAssertionFailure) -> Self;
+    }
+
+    impl SkipResult for () {
+        fn skipped(_: TestAssertionFailure) -> Self {}
+    }
+
+    impl<T, E: From<TestAssertionFailure>> SkipResult for Result<T, E> {
+        fn skipped(failure: TestAssertionFailure) -> Self {
+            Err(failure.into())
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         self as googletest,
-        assertions::{verify_eq, verify_that},
         matchers::{anything, err},
         test, Result as TestResult,
     };
@@ -0,0 +1,170 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Global test environments, set up once before the first test of the test
+//! binary and torn down after the last one.
+//!
+//! This mirrors `::testing::Environment` in GoogleTest C++. An environment is
+//! a type implementing [`Environment`] and [`Default`], registered with the
+//! attribute [`gtest_environment`][crate::gtest_environment]:
+//!
+//! ```ignore
+//! #[gtest_environment]
+//! #[derive(Default)]
+//! struct TestDatabase;
+//!
+//! impl Environment for TestDatabase {
+//!     fn set_up(&mut self) -> Result<()> {
+//!         start_database()
+//!     }
+//!
+//!     fn tear_down(&mut self) -> Result<()> {
+//!         stop_database()
+//!     }
+//! }
+//! ```
+//!
+//! Unlike a [`StaticFixture`][crate::fixtures::StaticFixture], which is set up
+//! lazily by the first test using it, all environments are set up when the
+//! first `#[gtest]` test of the binary starts, whether it uses them or not.
+//! Their order is unspecified. They are torn down in the reverse order after
+//! the last test, following the static fixtures.
+//!
+//! If an environment fails to set up, through an error, a non-fatal assertion
+//! failure or a panic, the remaining environments are not set up and every
+//! test fails with the failure of the environment.
+//!
+//! Registration relies on the constructor sections of the object file formats
+//! of Linux, Android, FreeBSD, NetBSD, OpenBSD, Apple platforms and Windows.
+//! [`gtest_environment`][crate::gtest_environment] does not compile on other
+//! targets.
+
+use crate::internal::test_outcome::{TestAssertionFailure, TestOutcome};
+use std::io::Write as _;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// A global test environment, set up before the first test and torn down
+/// after the last one.
+///
+/// See the [module documentation][self] for how to register an environment.
+pub trait Environment: Send + 'static {
+    /// Sets up the environment.
+    ///
+    /// An error or an assertion failure makes every test fail.
+    fn set_up(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    /// Tears down the environment after the last test, also if
+    /// [`Environment::set_up`] failed. Under the Rust test harness, this has
+    /// the same limitations as
+    /// [`StaticFixture::tear_down_once`][crate::fixtures::StaticFixture::tear_down_once].
+    ///
+    /// No test can fail at that point, so failures are only printed to the
+    /// standard error.
+    fn tear_down(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+type SharedEnvironment = Arc<Mutex<dyn Environment>>;
+
+/// The registered environments with the names of their types.
+static ENVIRONMENTS: Mutex<Vec<(&'static str, SharedEnvironment)>> = Mutex::new(Vec::new());
+
+/// Registers the environment `E`.
+///
+/// This is intended only for use by the attribute macro `#[gtest_environment]`,
+/// which calls it before `main`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn register_environment<E: Environment + Default>() {
+    ENVIRONMENTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push((std::any::type_name::<E>(), Arc::new(Mutex::new(E::default()))));
+}
+
+/// Sets up the registered environments unless already done, returning the
+/// failure of the environment which failed to set up, if any.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn set_up_environments() -> Result<(), TestAssertionFailure> {
+    static SET_UP: OnceLock<Result<(), TestAssertionFailure>> = OnceLock::new();
+    SET_UP
+        .get_or_init(|| {
+            let environments = ENVIRONMENTS.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            for (name, environment) in environments {
+                let tear_down_environment = environment.clone();
+                crate::fixtures::tear_down_after_last_test(Box::new(move || {
+                    let result = run_detached(|| lock(&tear_down_environment).tear_down());
+                    if let Err(e) = result {
+                        let _ = writeln!(
+                            std::io::stderr(),
+                            "Failed to tear down global test environment {name}:\n{e}"
+                        );
+                    }
+                }));
+                if let Err(mut failure) = run_detached(|| lock(&environment).set_up()) {
+                    failure.description = format!(
+                        "Global test environment {name} failed to set up:\n{}",
+                        failure.description
+                    );
+                    return Err(failure);
+                }
+            }
+            Ok(())
+        })
+        .clone()
+}
+
+fn lock(environment: &SharedEnvironment) -> std::sync::MutexGuard<'_, dyn Environment> {
+    environment.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs `action` on a separate thread, outside of any test, turning
+/// non-fatal assertion failures and panics into an error.
+fn run_detached(
+    action: impl FnOnce() -> crate::Result<()> + Send,
+) -> Result<(), TestAssertionFailure> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                TestOutcome::init_current_test_outcome();
+                let result = catch_unwind(AssertUnwindSafe(action)).unwrap_or_else(|payload| {
+                    let description = if let Some(message) = payload.downcast_ref::<&str>() {
+                        format!("Panicked: {message}")
+                    } else if let Some(message) = payload.downcast_ref::<String>() {
+                        format!("Panicked: {message}")
+                    } else {
+                        "Panicked.".into()
+                    };
+                    Err(TestAssertionFailure::create(description))
+                });
+                match TestOutcome::get_current_test_outcome() {
+                    Ok(()) => result,
+                    Err(_) => result.and(Err(TestAssertionFailure::create(
+                        "Recorded non-fatal assertion failures.".into(),
+                    ))),
+                }
+            })
+            .join()
+            .unwrap()
+    })
+}
@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::internal::test_report;
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
+    future::Future,
+    io::Write as _,
     ops::{Deref, DerefMut},
-    sync::{Mutex, OnceLock},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::atomic::{AtomicBool, Ordering},
+    sync::{Mutex, PoisonError},
 };
 
 /// Interface for structure to be set up and torn down as part of a test.
@@ -107,6 +112,9 @@ impl<T> DerefMut for FixtureOf<T> {
 /// #[gtest]
 /// fn test_with_fixture(my_fixture: &&MyFixture){...}
 /// ```
+///
+/// To share the fixture only between the tests of a module, wrap it in
+/// [`ModuleScoped`].
 pub trait StaticFixture: Sized + Sync + Send {
     /// Factory method of the `StaticFixture`.
     ///
@@ -114,21 +122,29 @@ pub trait StaticFixture: Sized + Sync + Send {
     /// using this fixture. If this method returns an `Err(...)`, then every
     /// test case using this fixture is not evaluated and automatically fails.
     fn set_up_once() -> crate::Result<Self>;
+
+    /// Clean up method for the fixture.
+    ///
+    /// This method is called once after the last `#[gtest]` test case of the
+    /// test binary has finished, if `set_up_once` succeeded. Static fixtures
+    /// are torn down in the reverse order of their set up. Under the Rust test
+    /// harness, this requires the tests to register themselves, which they
+    /// only do on the platforms supporting
+    /// [global test environments][crate::environment]. The fixture is not torn
+    /// down there if the test binary contains a `#[gtest]` test using rstest
+    /// or runs with an argument of the test harness unknown to GoogleTest
+    /// Rust, since the last test cannot be known then.
+    ///
+    /// No test case can fail at that point, so an error returned by this
+    /// method is only printed to the standard error.
+    fn tear_down_once(&self) -> crate::Result<()> {
+        Ok(())
+    }
 }
 
 impl<F: StaticFixture + 'static> Fixture for &'static F {
     fn set_up() -> crate::Result<Self> {
-        static ONCE_FIXTURE_REPO: OnceLock<
-            Mutex<HashMap<TypeId, &'static (dyn Any + Sync + Send)>>,
-        > = OnceLock::new();
-        let mut map = ONCE_FIXTURE_REPO.get_or_init(|| Mutex::new(HashMap::new())).lock()?;
-        let any =
-            map.entry(TypeId::of::<F>()).or_insert_with(|| Box::leak(Box::new(F::set_up_once())));
-        match any.downcast_ref::<crate::Result<F>>() {
-            Some(Ok(fixture)) => Ok(fixture),
-            Some(Err(e)) => Err(e.clone()),
-            None => panic!("Downcast failed. This is a bug in GoogleTest Rust"),
-        }
+        set_up_static_fixture(None)
     }
 
     // Note that this is `&F` being torn down, not `F`.
@@ -137,12 +153,530 @@ impl<F: StaticFixture + 'static> Fixture for &'static F {
     }
 }
 
+/// Generic adapter sharing a [`StaticFixture`] between the tests defined in
+/// the same module, rather than between all tests.
+///
+/// ```ignore
+/// struct MyServer { ... }
+///
+/// impl StaticFixture for MyServer { ... }
+///
+/// mod with_server {
+///     #[gtest]
+///     fn test_with_server(server: &ModuleScoped<MyServer>) {...}
+/// }
+/// ```
+///
+/// The fixture is set up before the first test case of the module using it,
+/// and torn down with [`StaticFixture::tear_down_once`] after the last test.
+/// Each case of a parameterized test belongs to the module in which the
+/// test is defined.
+pub struct ModuleScoped<F: 'static>(&'static F);
+
+impl<F: StaticFixture + 'static> Fixture for ModuleScoped<F> {
+    fn set_up() -> crate::Result<Self> {
+        let module = test_report::running_test_module().ok_or_else(|| {
+            crate::TestAssertionFailure::create(
+                "ModuleScoped fixtures can only be used in tests annotated with #[gtest]".into(),
+            )
+        })?;
+        Ok(Self(set_up_static_fixture(Some(module))?))
+    }
+
+    fn tear_down(self) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+impl<F> Deref for ModuleScoped<F> {
+    type Target = F;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+/// Identifies a set up static fixture by its type and, for a [`ModuleScoped`]
+/// fixture, its module.
+type StaticFixtureKey = (TypeId, Option<&'static str>);
+
+/// The `tear_down_once` methods of the static fixtures set up so far, and the
+/// tear down of the global test environments, in the order of their set up.
+static STATIC_TEAR_DOWNS: Mutex<Vec<Box<dyn FnOnce() + Send>>> = Mutex::new(Vec::new());
+
+/// The static fixtures set up so far, each a leaked `crate::Result<F>`. The map
+/// is created with the first of them and dropped once they are torn down.
+static ONCE_FIXTURE_REPO: Mutex<
+    Option<HashMap<StaticFixtureKey, &'static (dyn Any + Sync + Send)>>,
+> = Mutex::new(None);
+
+/// Whether the static fixtures have been torn down after the last test.
+static STATIC_FIXTURES_TORN_DOWN: AtomicBool = AtomicBool::new(false);
+
+fn set_up_static_fixture<F: StaticFixture + 'static>(
+    module: Option<&'static str>,
+) -> crate::Result<&'static F> {
+    let mut repo = ONCE_FIXTURE_REPO.lock()?;
+    if STATIC_FIXTURES_TORN_DOWN.load(Ordering::Relaxed) {
+        return Err(crate::TestAssertionFailure::create(format!(
+            "The static fixture {} cannot be used after the static fixtures have been torn \
+             down at the end of the last test.",
+            std::any::type_name::<F>()
+        )));
+    }
+    let map = repo.get_or_insert_with(HashMap::new);
+    let any = map.entry((TypeId::of::<F>(), module)).or_insert_with(|| {
+        let result: &'static crate::Result<F> = Box::leak(Box::new(F::set_up_once()));
+        if let Ok(fixture) = result {
+            notify_set_up::<F>();
+            register_tear_down_once(fixture);
+        }
+        result
+    });
+    match any.downcast_ref::<crate::Result<F>>() {
+        Some(Ok(fixture)) => Ok(fixture),
+        Some(Err(e)) => Err(e.clone()),
+        None => panic!("Downcast failed. This is a bug in GoogleTest Rust"),
+    }
+}
+
+fn register_tear_down_once<F: StaticFixture>(fixture: &'static F) {
+    let tear_down = move || {
+        let result = fixture.tear_down_once();
+        notify_tear_down::<F>();
+        if let Err(e) = result {
+            // This may run at the end of the last test, whose output the Rust
+            // test harness captures.
+            let _ = writeln!(
+                std::io::stderr(),
+                "Failed to tear down {}:\n{e}",
+                std::any::type_name::<F>()
+            );
+        }
+    };
+    tear_down_after_last_test(Box::new(tear_down));
+}
+
+/// Registers `tear_down` to run with [`tear_down_static_fixtures`], before
+/// those registered earlier.
+///
+/// Global test environments are torn down this way as well, so that they
+/// outlive the static fixtures set up by the tests.
+pub(crate) fn tear_down_after_last_test(tear_down: Box<dyn FnOnce() + Send>) {
+    STATIC_TEAR_DOWNS.lock().unwrap_or_else(PoisonError::into_inner).push(tear_down);
+}
+
+/// Notifies the test event listeners that the fixture `F` has been set up.
+pub(crate) fn notify_set_up<F>() {
+    crate::listener::notify(|listener| listener.on_fixture_set_up(std::any::type_name::<F>()));
+}
+
+/// Notifies the test event listeners that the fixture `F` has been torn down.
+pub(crate) fn notify_tear_down<F>() {
+    crate::listener::notify(|listener| listener.on_fixture_tear_down(std::any::type_name::<F>()));
+}
+
+/// Tears down the static fixtures and global test environments set up so far,
+/// in the reverse order of their set up.
+///
+/// This is called by the runner of `googletest::main!()` after all tests, and
+/// under the Rust test harness when the last test it runs ends.
+pub(crate) fn tear_down_static_fixtures() {
+    {
+        // Later set ups fail rather than handing out a fixture torn down here.
+        let mut repo = ONCE_FIXTURE_REPO.lock().unwrap_or_else(PoisonError::into_inner);
+        STATIC_FIXTURES_TORN_DOWN.store(true, Ordering::Relaxed);
+        *repo = None;
+    }
+    let tear_downs =
+        std::mem::take(&mut *STATIC_TEAR_DOWNS.lock().unwrap_or_else(PoisonError::into_inner));
+    for tear_down in tear_downs.into_iter().rev() {
+        // The panic message is printed by the panic hook.
+        let _ = catch_unwind(AssertUnwindSafe(tear_down));
+    }
+}
+
+/// Interface for structure to be set up with other fixtures as inputs.
+/// Types implementing `DependentFixture` can be passed as a reference argument
+/// to a synchronous test function, like a [`Fixture`].
+///
+/// The fixtures a `DependentFixture` depends on are listed as a tuple in
+/// [`DependentFixture::Dependencies`] and passed to
+/// [`DependentFixture::set_up`] as a tuple of references:
+///
+/// ```ignore
+/// struct Db { ... }
+///
+/// impl DependentFixture for Db {
+///     type Dependencies = (TempDir, Config);
+///
+///     fn set_up((tmp, config): (&TempDir, &Config)) -> googletest::Result<Self> { ... }
+///
+///     fn tear_down(self) -> googletest::Result<()> { ... }
+/// }
+///
+/// #[gtest]
+/// fn test_with_db(tmp: &TempDir, db: &Db) {...}
+/// ```
+///
+/// The test harness sets up the dependencies of a fixture before the fixture
+/// itself, and tears fixtures down in the reverse order. Within a test, each
+/// dependency is set up once and shared: above, `tmp` is the `TempDir` that
+/// `Db` was set up with. A cycle of dependencies fails the test.
+///
+/// Every [`Fixture`] is a `DependentFixture` without dependencies.
+pub trait DependentFixture: Sized + 'static {
+    /// The fixtures this fixture depends on, as a tuple of fixture types.
+    type Dependencies: FixtureDependencies;
+
+    /// Factory method of the `DependentFixture`.
+    ///
+    /// This method is called by the test harness before the test case
+    /// that uses this fixture, once all its dependencies are set up. If this
+    /// method returns an `Err(...)`, then the test case is not evaluated,
+    /// automatically fails, and only the fixtures previously set up are torn
+    /// down.
+    fn set_up(
+        dependencies: <Self::Dependencies as FixtureDependencies>::Refs<'_>,
+    ) -> crate::Result<Self>;
+
+    /// Clean up method for the fixture.
+    ///
+    /// This method is called by the test harness after the test case
+    /// that uses this fixture, before its dependencies are torn down. If the
+    /// `DependentFixture` has been set up, the test harness will call this
+    /// method, even if the test case failed or panicked.
+    fn tear_down(self) -> crate::Result<()>;
+}
+
+impl<F: Fixture + 'static> DependentFixture for F {
+    type Dependencies = ();
+
+    fn set_up((): ()) -> crate::Result<Self> {
+        <F as Fixture>::set_up()
+    }
+
+    fn tear_down(self) -> crate::Result<()> {
+        <F as Fixture>::tear_down(self)
+    }
+}
+
+/// A tuple of fixture types on which a [`DependentFixture`] depends.
+///
+/// This is implemented for tuples of up to eight [`DependentFixture`] types.
+pub trait FixtureDependencies {
+    /// The references to the set up fixtures passed to
+    /// [`DependentFixture::set_up`].
+    type Refs<'a>;
+
+    /// Sets up the fixtures which are not yet set up in `scope`.
+    ///
+    /// **For internal use only. API stablility is not guaranteed!**
+    #[doc(hidden)]
+    fn set_up(scope: &mut FixtureScope) -> crate::Result<()>;
+
+    /// Returns references to the fixtures set up in `scope`.
+    ///
+    /// **For internal use only. API stablility is not guaranteed!**
+    #[doc(hidden)]
+    fn get(scope: &FixtureScope) -> Self::Refs<'_>;
+}
+
+macro_rules! impl_fixture_dependencies {
+    ($($fixture:ident),*) => {
+        impl<$($fixture: DependentFixture),*> FixtureDependencies for ($($fixture,)*) {
+            type Refs<'a> = ($(&'a $fixture,)*);
+
+            #[allow(unused_variables)]
+            fn set_up(scope: &mut FixtureScope) -> crate::Result<()> {
+                $(scope.set_up_dependency::<$fixture>()?;)*
+                Ok(())
+            }
+
+            #[allow(unused_variables, clippy::unused_unit)]
+            fn get(scope: &FixtureScope) -> Self::Refs<'_> {
+                ($(scope.get::<$fixture>(),)*)
+            }
+        }
+    };
+}
+
+impl_fixture_dependencies!();
+impl_fixture_dependencies!(A);
+impl_fixture_dependencies!(A, B);
+impl_fixture_dependencies!(A, B, C);
+impl_fixture_dependencies!(A, B, C, D);
+impl_fixture_dependencies!(A, B, C, D, E);
+impl_fixture_dependencies!(A, B, C, D, E, F);
+impl_fixture_dependencies!(A, B, C, D, E, F, G);
+impl_fixture_dependencies!(A, B, C, D, E, F, G, H);
+
+/// The [`DependentFixture`]s set up for a test, in the order in which they
+/// were set up.
+///
+/// Each fixture argument of the test gets its own instance, except that the
+/// first argument of a type takes over the instance set up as a dependency of
+/// an earlier argument, if any. Dependencies resolve to the first instance of
+/// their type.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[derive(Default)]
+pub struct FixtureScope {
+    fixtures: Vec<ScopedFixture>,
+    /// The fixtures being set up, each being a dependency of the previous one.
+    in_progress: Vec<(TypeId, &'static str)>,
+}
+
+struct ScopedFixture {
+    type_id: TypeId,
+    /// Whether the fixture was passed to the test as an argument.
+    claimed: bool,
+    fixture: Box<dyn ErasedFixture>,
+}
+
+/// Object safe interface of [`DependentFixture`].
+trait ErasedFixture {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn tear_down(self: Box<Self>) -> crate::Result<()>;
+}
+
+impl<F: DependentFixture> ErasedFixture for F {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn tear_down(self: Box<Self>) -> crate::Result<()> {
+        let result = DependentFixture::tear_down(*self);
+        notify_tear_down::<F>();
+        result
+    }
+}
+
+impl FixtureScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets up the fixture `F` for a test argument and returns its index, to
+    /// be passed to [`FixtureArguments::take`].
+    pub fn set_up_argument<F: DependentFixture>(&mut self) -> crate::Result<usize> {
+        let type_id = TypeId::of::<F>();
+        let index = match self
+            .fixtures
+            .iter()
+            .position(|fixture| fixture.type_id == type_id && !fixture.claimed)
+        {
+            Some(index) => index,
+            None => self.set_up_fixture::<F>()?,
+        };
+        self.fixtures[index].claimed = true;
+        Ok(index)
+    }
+
+    /// Borrows the fixtures set up by [`FixtureScope::set_up_argument`].
+    pub fn arguments(&mut self) -> FixtureArguments<'_> {
+        FixtureArguments {
+            fixtures: self
+                .fixtures
+                .iter_mut()
+                .map(|fixture| Some(fixture.fixture.as_any_mut()))
+                .collect(),
+        }
+    }
+
+    /// Tears down all fixtures, in the reverse order of their set up.
+    ///
+    /// Returns the first error returned by a fixture, if any.
+    pub fn tear_down(mut self) -> crate::Result<()> {
+        let mut result = Ok(());
+        while let Some(fixture) = self.fixtures.pop() {
+            let tear_down_result = fixture.fixture.tear_down();
+            result = result.and(tear_down_result);
+        }
+        result
+    }
+
+    fn set_up_dependency<F: DependentFixture>(&mut self) -> crate::Result<()> {
+        let type_id = TypeId::of::<F>();
+        if !self.fixtures.iter().any(|fixture| fixture.type_id == type_id) {
+            self.set_up_fixture::<F>()?;
+        }
+        Ok(())
+    }
+
+    fn get<F: DependentFixture>(&self) -> &F {
+        let type_id = TypeId::of::<F>();
+        self.fixtures
+            .iter()
+            .find(|fixture| fixture.type_id == type_id)
+            .and_then(|fixture| fixture.fixture.as_any().downcast_ref())
+            .expect("Dependency not set up. This is a bug in GoogleTest Rust")
+    }
+
+    fn set_up_fixture<F: DependentFixture>(&mut self) -> crate::Result<usize> {
+        let type_id = TypeId::of::<F>();
+        let type_name = std::any::type_name::<F>();
+        if let Some(start) = self.in_progress.iter().position(|(id, _)| *id == type_id) {
+            let cycle = self.in_progress[start..]
+                .iter()
+                .map(|(_, name)| *name)
+                .chain([type_name])
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(crate::TestAssertionFailure::create(format!(
+                "Cycle in the dependencies of fixtures: {cycle}"
+            )));
+        }
+        self.in_progress.push((type_id, type_name));
+        let fixture =
+            F::Dependencies::set_up(self).and_then(|()| F::set_up(F::Dependencies::get(self)));
+        self.in_progress.pop();
+        let fixture = Box::new(fixture?);
+        notify_set_up::<F>();
+        self.fixtures.push(ScopedFixture { type_id, claimed: false, fixture });
+        Ok(self.fixtures.len() - 1)
+    }
+}
+
+impl Drop for FixtureScope {
+    // Only reached with fixtures left if the test did not run to
+    // `FixtureScope::tear_down`, because it panicked or a set up failed.
+    fn drop(&mut self) {
+        while let Some(fixture) = self.fixtures.pop() {
+            if let Err(failure) = fixture.fixture.tear_down() {
+                // The test already fails because of the panic.
+                if !std::thread::panicking() {
+                    failure.log();
+                }
+            }
+        }
+    }
+}
+
+/// The fixtures of a [`FixtureScope`], borrowed to be passed to the test.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct FixtureArguments<'a> {
+    fixtures: Vec<Option<&'a mut dyn Any>>,
+}
+
+impl<'a> FixtureArguments<'a> {
+    /// Takes the fixture at `index`, as returned by
+    /// [`FixtureScope::set_up_argument`].
+    pub fn take<F: DependentFixture>(&mut self, index: usize) -> &'a mut F {
+        self.fixtures[index]
+            .take()
+            .and_then(|fixture| fixture.downcast_mut())
+            .expect("Fixture taken twice. This is a bug in GoogleTest Rust")
+    }
+}
+
+/// Interface for structure to be set up and torn down asynchronously as part
+/// of an `async` test.
+/// Types implementing `AsyncFixture` can be passed as a reference argument to
+/// an `async` test function.
+///
+/// ```ignore
+/// struct MyServer { ... }
+///
+/// impl AsyncFixture for MyServer {
+///     async fn set_up() -> googletest::Result<Self> { ... }
+///
+///     async fn tear_down(self) -> googletest::Result<()> { ... }
+/// }
+///
+/// #[gtest]
+/// #[tokio::test]
+/// async fn test_with_server(server: &MyServer) {...}
+/// ```
+///
+/// Every [`Fixture`] is also an `AsyncFixture`, so that `async` tests can use
+/// synchronous fixtures as well.
+pub trait AsyncFixture: Sized {
+    /// Factory method of the `AsyncFixture`.
+    ///
+    /// This method is awaited by the test harness before the test case
+    /// that uses this fixture. If this method returns an `Err(...)`,
+    /// then the test case is not evaluated, automatically fails, and
+    /// only the fixtures previously set up are torn down.
+    fn set_up() -> impl Future<Output = crate::Result<Self>>;
+
+    /// Clean up method for the fixture.
+    ///
+    /// This method is awaited by the test harness after the test case
+    /// that uses this fixture, even if the test case failed or panicked.
+    ///
+    /// This includes a test case cancelled because it exceeded its timeout.
+    /// The fixture is only dropped without being torn down if the whole test
+    /// is dropped before completing, for instance by its async runtime.
+    fn tear_down(self) -> impl Future<Output = crate::Result<()>>;
+}
+
+impl<F: Fixture> AsyncFixture for F {
+    async fn set_up() -> crate::Result<Self> {
+        <F as Fixture>::set_up()
+    }
+
+    async fn tear_down(self) -> crate::Result<()> {
+        <F as Fixture>::tear_down(self)
+    }
+}
+
+/// Interface for structure to be set up asynchronously before an `async` test
+/// case.
+/// Types implementing `AsyncConsumableFixture` can be passed by value to an
+/// `async` test function.
+///
+/// ```ignore
+/// struct MyPool { ... }
+///
+/// impl AsyncConsumableFixture for MyPool {
+///     async fn set_up() -> googletest::Result<Self> { ... }
+/// }
+///
+/// #[gtest]
+/// #[tokio::test]
+/// async fn test_with_pool(pool: MyPool) {...}
+/// ```
+///
+/// Every [`ConsumableFixture`] is also an `AsyncConsumableFixture`.
+pub trait AsyncConsumableFixture: Sized {
+    /// Factory method of the `AsyncConsumableFixture`.
+    ///
+    /// This method is awaited by the test harness before the test case
+    /// that uses this fixture. If this method returns an `Err(...)`,
+    /// then the test case is not evaluated, automatically fails, and
+    /// only the fixtures previously set up are torn down.
+    fn set_up() -> impl Future<Output = crate::Result<Self>>;
+}
+
+impl<F: ConsumableFixture> AsyncConsumableFixture for F {
+    async fn set_up() -> crate::Result<Self> {
+        <F as ConsumableFixture>::set_up()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use std::cell::RefCell;
+    use std::rc::Rc;
     use std::sync::Once;
 
+    use super::DependentFixture;
     use super::FixtureOf;
+    use super::FixtureScope;
     use super::StaticFixture;
     use crate as googletest;
     use crate::prelude::*;
@@ -314,4 +848,123 @@ mod tests {
     #[test]
     #[should_panic(expected = "See failure output above")]
     fn failing_teardown_causes_test_failure(_: &FailingTearDown) {}
+
+    #[test]
+    #[should_panic(expected = "See failure output above")]
+    fn failing_teardown_after_failing_set_up_causes_test_failure(
+        _: &FailingTearDown,
+        _: &FailingSetUp,
+    ) {
+        unreachable!()
+    }
+
+    #[derive(Default)]
+    struct EventLog(Rc<RefCell<Vec<&'static str>>>);
+
+    impl Fixture for EventLog {
+        fn set_up() -> crate::Result<Self> {
+            Ok(Self::default())
+        }
+
+        fn tear_down(self) -> crate::Result<()> {
+            verify_that!(
+                *self.0.borrow(),
+                elements_are![
+                    eq(&"set up Db"),
+                    eq(&"set up Cache"),
+                    eq(&"tear down Cache"),
+                    eq(&"tear down Db"),
+                ]
+            )
+        }
+    }
+
+    struct Db(Rc<RefCell<Vec<&'static str>>>);
+
+    impl DependentFixture for Db {
+        type Dependencies = (EventLog,);
+
+        fn set_up((log,): (&EventLog,)) -> crate::Result<Self> {
+            log.0.borrow_mut().push("set up Db");
+            Ok(Self(log.0.clone()))
+        }
+
+        fn tear_down(self) -> crate::Result<()> {
+            self.0.borrow_mut().push("tear down Db");
+            Ok(())
+        }
+    }
+
+    struct Cache(Rc<RefCell<Vec<&'static str>>>);
+
+    impl DependentFixture for Cache {
+        type Dependencies = (Db, EventLog);
+
+        fn set_up((db, log): (&Db, &EventLog)) -> crate::Result<Self> {
+            verify_that!(Rc::ptr_eq(&db.0, &log.0), eq(true))?;
+            log.0.borrow_mut().push("set up Cache");
+            Ok(Self(log.0.clone()))
+        }
+
+        fn tear_down(self) -> crate::Result<()> {
+            self.0.borrow_mut().push("tear down Cache");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn dependent_fixtures_set_up_in_dependency_order(_: &Cache) {}
+
+    #[test]
+    fn dependent_fixture_shares_dependency_with_test(log: &EventLog, cache: &mut Cache) {
+        expect_that!(Rc::ptr_eq(&log.0, &cache.0), eq(true));
+    }
+
+    #[test]
+    fn dependency_set_up_after_dependent_fixture_is_shared(cache: &Cache, log: &EventLog) {
+        expect_that!(Rc::ptr_eq(&log.0, &cache.0), eq(true));
+    }
+
+    struct CycleStart;
+
+    impl DependentFixture for CycleStart {
+        type Dependencies = (CycleEnd,);
+
+        fn set_up(_: (&CycleEnd,)) -> crate::Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down(self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct CycleEnd;
+
+    impl DependentFixture for CycleEnd {
+        type Dependencies = (CycleStart,);
+
+        fn set_up(_: (&CycleStart,)) -> crate::Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down(self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn dependency_cycle_is_an_error() -> Result<()> {
+        let result = FixtureScope::new().set_up_argument::<CycleStart>();
+
+        verify_that!(
+            result,
+            err(displays_as(contains_substring(
+                "Cycle in the dependencies of fixtures: \
+                 googletest::fixtures::tests::CycleStart -> \
+                 googletest::fixtures::tests::CycleEnd -> \
+                 googletest::fixtures::tests::CycleStart"
+            )))
+        )
+    }
 }
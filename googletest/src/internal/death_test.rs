@@ -0,0 +1,242 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for the death test macros `expect_exit!` and `expect_death!`.
+//!
+//! The statement of a death test is not run in the process of the test.
+//! Instead, the test binary is executed again, restricted to the running test,
+//! with the environment variable GTEST_INTERNAL_RUN_DEATH_TEST set to the
+//! location of the death test. In that child process, the test runs as usual
+//! until it reaches the death test, which runs its statement and exits the
+//! process if the statement returns. Other death tests in the child are
+//! skipped. The parent then matches the exit status and the standard error of
+//! the child, written after the statement started, against the given matchers.
+
+use crate::internal::test_main;
+use crate::internal::test_outcome::TestAssertionFailure;
+use crate::matcher::{Matcher, MatcherResult};
+use crate::TestInfo;
+use std::fmt::Debug;
+use std::io::Write as _;
+use std::process::{Command, ExitStatus, Output};
+
+/// Environment variable holding the location of the death test whose
+/// statement the child process should run.
+const GTEST_INTERNAL_RUN_DEATH_TEST: &str = "GTEST_INTERNAL_RUN_DEATH_TEST";
+
+/// Environment variables which the child process must not inherit, since they
+/// would make it write reports, list, filter or repeat tests on behalf of the
+/// parent, or stop at failures as if a debugger were attached to it.
+const PARENT_ONLY_VARIABLES: &[&str] = &[
+    "GTEST_BREAK_ON_FAILURE",
+    "GTEST_FILTER",
+    "GTEST_LIST_TESTS",
+    "GTEST_OUTPUT",
+    "GTEST_REPEAT",
+    "GTEST_RETRIES",
+    "GTEST_SHARD_INDEX",
+    "GTEST_SHARD_STATUS_FILE",
+    "GTEST_TOTAL_SHARDS",
+    "TESTBRIDGE_TEST_ONLY",
+    "TEST_SHARD_INDEX",
+    "TEST_SHARD_STATUS_FILE",
+    "TEST_TOTAL_SHARDS",
+    "XML_OUTPUT_FILE",
+];
+
+/// Written by the child to the standard error just before running the
+/// statement.
+const STATEMENT_STARTED: &str = "[googletest death test statement started]\n";
+
+/// Written by the child to the standard error if the statement returns.
+const STATEMENT_RETURNED: &str = "[googletest death test statement returned]\n";
+
+/// Runs the death test at `location`, expecting its statement `statement`,
+/// whose source is `statement_expr`, to exit the process with an exit code
+/// matched by `exit_code_matcher`.
+///
+/// This is intended only for use by the macro `verify_exit!`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[track_caller]
+pub fn run_exit_test(
+    location: &'static str,
+    statement_expr: &'static str,
+    statement: impl FnOnce(),
+    exit_code_matcher: impl Matcher<i32>,
+    stderr_matcher: impl for<'a> Matcher<&'a str>,
+) -> Result<(), TestAssertionFailure> {
+    let Some((status, stderr)) = run_death_test(location, statement_expr, statement)? else {
+        return Ok(());
+    };
+    let Some(exit_code) = status.code() else {
+        return Err(TestAssertionFailure::create(format!(
+            "The statement `{statement_expr}` of the death test was expected to exit, but the \
+            process {}.\nStderr:\n{stderr}",
+            describe_status(&status)
+        )));
+    };
+    check(&exit_code_matcher, exit_code, "exit code", statement_expr)?;
+    check(&stderr_matcher, stderr.as_str(), "stderr", statement_expr)
+}
+
+/// Runs the death test at `location`, expecting its statement `statement`,
+/// whose source is `statement_expr`, to exit the process with a non-zero exit
+/// code or to kill it with a signal.
+///
+/// This is intended only for use by the macro `verify_death!`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[track_caller]
+pub fn run_death_test_expecting_death(
+    location: &'static str,
+    statement_expr: &'static str,
+    statement: impl FnOnce(),
+    stderr_matcher: impl for<'a> Matcher<&'a str>,
+) -> Result<(), TestAssertionFailure> {
+    let Some((status, stderr)) = run_death_test(location, statement_expr, statement)? else {
+        return Ok(());
+    };
+    if status.success() {
+        return Err(TestAssertionFailure::create(format!(
+            "The statement `{statement_expr}` of the death test was expected to die, but the \
+            process {}.\nStderr:\n{stderr}",
+            describe_status(&status)
+        )));
+    }
+    check(&stderr_matcher, stderr.as_str(), "stderr", statement_expr)
+}
+
+/// Runs the death test at `location` in a child process and returns how the
+/// child terminated together with what it wrote to the standard error while
+/// running the statement.
+///
+/// In the child process, this runs `statement` instead and returns `None` if
+/// the death test at `location` is not the one the child is running.
+#[track_caller]
+fn run_death_test(
+    location: &'static str,
+    statement_expr: &'static str,
+    statement: impl FnOnce(),
+) -> Result<Option<(ExitStatus, String)>, TestAssertionFailure> {
+    if let Some(death_test) = std::env::var_os(GTEST_INTERNAL_RUN_DEATH_TEST) {
+        if death_test == location {
+            run_statement(statement);
+        }
+        return Ok(None);
+    }
+    let Some(test_info) = TestInfo::current() else {
+        return Err(TestAssertionFailure::create(
+            "Death tests can only run inside a test annotated with #[gtest].".into(),
+        ));
+    };
+    let output = match run_child(&test_info, location) {
+        Ok(output) => output,
+        Err(error) => {
+            return Err(TestAssertionFailure::create(format!(
+                "Failed to run the death test `{statement_expr}` in a child process: {error}"
+            )));
+        }
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let Some((_, statement_stderr)) = stderr.split_once(STATEMENT_STARTED) else {
+        return Err(TestAssertionFailure::create(format!(
+            "The child process of the death test `{statement_expr}` did not reach the \
+            statement.\nThe process {}.\nStdout:\n{}\nStderr:\n{stderr}",
+            describe_status(&output.status),
+            String::from_utf8_lossy(&output.stdout),
+        )));
+    };
+    if let Some((statement_stderr, _)) = statement_stderr.split_once(STATEMENT_RETURNED) {
+        return Err(TestAssertionFailure::create(format!(
+            "The statement `{statement_expr}` of the death test returned instead of \
+            terminating the process.\nStderr:\n{statement_stderr}"
+        )));
+    }
+    Ok(Some((output.status, statement_stderr.to_string())))
+}
+
+/// Runs the statement of the death test in the child process and exits the
+/// process if it returns.
+fn run_statement(statement: impl FnOnce()) -> ! {
+    let _ = std::io::stderr().write_all(STATEMENT_STARTED.as_bytes());
+    statement();
+    let _ = std::io::stderr().write_all(STATEMENT_RETURNED.as_bytes());
+    std::process::exit(0);
+}
+
+/// Runs the test described by `test_info` in a child process which runs the
+/// death test at `location`.
+fn run_child(test_info: &TestInfo, location: &str) -> std::io::Result<Output> {
+    let mut command = Command::new(std::env::current_exe()?);
+    if test_main::is_running() {
+        // The runner of `googletest::main!()` filters tests by their full name,
+        // which contains no wildcard.
+        command.arg(format!("--gtest_filter={}", test_info.full_name()));
+    } else {
+        // The test harness names tests by their path relative to the crate root.
+        let test_name = test_info
+            .full_name()
+            .split_once("::")
+            .map_or(test_info.full_name(), |(_, test_name)| test_name);
+        command.args([
+            test_name,
+            "--exact",
+            "--nocapture",
+            "--include-ignored",
+            "--test-threads=1",
+        ]);
+    }
+    command.env(GTEST_INTERNAL_RUN_DEATH_TEST, location).env("GTEST_ALSO_RUN_DISABLED_TESTS", "1");
+    for variable in PARENT_ONLY_VARIABLES {
+        command.env_remove(variable);
+    }
+    command.output()
+}
+
+fn describe_status(status: &ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exited with code {code}");
+    }
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(status) {
+        return format!("was killed by signal {signal}");
+    }
+    format!("terminated with {status}")
+}
+
+#[track_caller]
+fn check<T: Debug + Copy>(
+    matcher: &impl Matcher<T>,
+    actual: T,
+    what: &str,
+    statement_expr: &str,
+) -> Result<(), TestAssertionFailure> {
+    match matcher.matches(actual) {
+        MatcherResult::Match => Ok(()),
+        MatcherResult::NoMatch => {
+            let description = matcher.describe(MatcherResult::Match);
+            Err(TestAssertionFailure::create(format!(
+                "\
+Value of: {what} of death test `{statement_expr}`
+Expected: {description}
+Actual: {actual:?},
+{}",
+                matcher.explain_match(actual).indent()
+            )))
+        }
+    }
+}
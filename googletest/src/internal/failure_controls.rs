@@ -0,0 +1,125 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for GTEST_BREAK_ON_FAILURE and GTEST_MAX_FAILURES_PER_TEST.
+//!
+//! If GTEST_BREAK_ON_FAILURE is set to a value other than `0`, the first
+//! assertion failure stops the process with a debugger trap where available,
+//! and aborts it otherwise. A debugger then stops at a failing non-fatal
+//! assertion such as `expect_that!`. A fatal failure such as that of
+//! `verify_that!(...)?` only stops the process once the test has returned it,
+//! as it may also be handled by the test.
+//!
+//! GTEST_MAX_FAILURES_PER_TEST limits the number of non-fatal failures of a
+//! test: the failure reaching the limit ends the test with a panic, so that an
+//! assertion in a long loop does not repeat the same failure indefinitely. If
+//! the limit is reached on a thread spawned by the test, the panic only ends
+//! that thread, and the test itself stops at its next failure or once its body
+//! returns.
+
+use crate::internal::test_report;
+use std::fmt::Display;
+use std::io::Write as _;
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
+
+const GTEST_BREAK_ON_FAILURE: &str = "GTEST_BREAK_ON_FAILURE";
+
+const GTEST_MAX_FAILURES_PER_TEST: &str = "GTEST_MAX_FAILURES_PER_TEST";
+
+/// Applies the controls to the non-fatal `failure` which has just been
+/// recorded against the running test.
+pub(crate) fn on_non_fatal_failure(failure: &dyn Display) {
+    break_if_requested(failure);
+    let Some(max_failures) = max_failures_per_test() else {
+        return;
+    };
+    // Panicking while unwinding would abort the process.
+    if test_report::failure_count() >= max_failures.get() && !std::thread::panicking() {
+        test_report::record_max_failures_reached();
+        stop_test(max_failures);
+    }
+}
+
+/// Ends the running test if another of its threads stopped at the maximum
+/// number of failures.
+pub(crate) fn on_test_end() {
+    let Some(max_failures) = max_failures_per_test() else {
+        return;
+    };
+    if test_report::max_failures_reached() && !std::thread::panicking() {
+        stop_test(max_failures);
+    }
+}
+
+fn stop_test(max_failures: NonZeroUsize) -> ! {
+    panic!(
+        "Stopping the test after {max_failures} failures as set by {GTEST_MAX_FAILURES_PER_TEST}."
+    );
+}
+
+/// Stops the process at `failure` if GTEST_BREAK_ON_FAILURE is set.
+///
+/// This applies to fatal failures as well, once the test has returned them.
+pub(crate) fn break_if_requested(failure: &dyn Display) {
+    if break_on_failure() {
+        // The output of the test harness is lost when the process stops.
+        let _ = std::io::stderr().write_all(
+            format!("{failure}\nStopping at this failure as {GTEST_BREAK_ON_FAILURE} is set.\n")
+                .as_bytes(),
+        );
+        break_into_debugger();
+    }
+}
+
+fn break_on_failure() -> bool {
+    static BREAK_ON_FAILURE: OnceLock<bool> = OnceLock::new();
+    *BREAK_ON_FAILURE
+        .get_or_init(|| std::env::var(GTEST_BREAK_ON_FAILURE).is_ok_and(|value| value != "0"))
+}
+
+fn max_failures_per_test() -> Option<NonZeroUsize> {
+    static MAX_FAILURES_PER_TEST: OnceLock<Option<NonZeroUsize>> = OnceLock::new();
+    *MAX_FAILURES_PER_TEST.get_or_init(|| {
+        let value = std::env::var(GTEST_MAX_FAILURES_PER_TEST).ok()?;
+        match value.parse() {
+            Ok(max_failures) => Some(max_failures),
+            Err(_) if value == "0" => None,
+            Err(e) => {
+                eprintln!("Ignoring {GTEST_MAX_FAILURES_PER_TEST}={value}: {e}");
+                None
+            }
+        }
+    })
+}
+
+/// Raises SIGTRAP, which stops the process in a debugger and terminates it
+/// otherwise, and aborts if the signal is ignored.
+#[cfg(unix)]
+fn break_into_debugger() -> ! {
+    extern "C" {
+        fn raise(signal: std::ffi::c_int) -> std::ffi::c_int;
+    }
+    const SIGTRAP: std::ffi::c_int = 5;
+    // SAFETY: `raise` has no preconditions.
+    unsafe {
+        raise(SIGTRAP);
+    }
+    std::process::abort()
+}
+
+#[cfg(not(unix))]
+fn break_into_debugger() -> ! {
+    std::process::abort()
+}
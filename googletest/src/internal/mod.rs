@@ -14,10 +14,19 @@
 
 #![doc(hidden)]
 
+pub mod death_test;
 pub(crate) mod description_renderer;
+pub(crate) mod failure_controls;
 pub mod glob;
 pub mod scoped_trace;
 pub mod test_data;
+pub mod test_end;
+pub mod test_expected_failure;
 pub mod test_filter;
+pub mod test_listing;
+pub mod test_main;
 pub mod test_outcome;
+pub mod test_report;
+pub mod test_retry;
 pub mod test_sharding;
+pub mod test_timeout;
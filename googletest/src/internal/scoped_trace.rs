@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
 static NEXT_TRACE_ID: AtomicUsize = AtomicUsize::new(0);
 
@@ -27,14 +30,32 @@ pub struct TraceInfo {
 }
 
 thread_local! {
-    static TRACE_STACK: RefCell<Vec<TraceInfo>> = const { RefCell::new(Vec::new()) };
+    // Shared so that the stack of a thread can be inspected from another
+    // thread, e.g., when a test running on a worker thread times out.
+    static TRACE_STACK: Arc<Mutex<Vec<TraceInfo>>> = Arc::default();
+}
+
+/// A handle to the trace stack of a thread, which can be read from any thread.
+#[derive(Clone)]
+pub(crate) struct TraceStack(Arc<Mutex<Vec<TraceInfo>>>);
+
+impl TraceStack {
+    /// Returns the handle to the trace stack of the current thread.
+    pub(crate) fn current() -> Self {
+        Self(TRACE_STACK.with(Arc::clone))
+    }
+
+    /// Retrieves a clone of the traces currently on the stack.
+    pub(crate) fn traces(&self) -> Vec<TraceInfo> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
 }
 
 /// RAII guard to manage the push and pop of trace information.
 ///
 /// This struct is `!Send` and `!Sync` to prevent it from being held across
 /// `.await` points in async tests, which would cause incorrect trace tracking
-/// if the task moves between threads.
+/// if the task moves between threads. Use [`with_trace`] for async code.
 #[doc(hidden)]
 pub struct ScopedTraceGuard {
     id: usize,
@@ -47,10 +68,12 @@ impl ScopedTraceGuard {
     pub fn new(message: String) -> Self {
         let caller = std::panic::Location::caller();
         let id = NEXT_TRACE_ID.fetch_add(1, Ordering::Relaxed);
+        let trace = TraceInfo { id, file: caller.file(), line: caller.line(), message };
+        crate::listener::notify(|listener| listener.on_trace_pushed(&trace));
         TRACE_STACK.with(|stack| {
-            // Use try_borrow_mut to avoid double panic if called during unwinding.
-            if let Ok(mut s) = stack.try_borrow_mut() {
-                s.push(TraceInfo { id, file: caller.file(), line: caller.line(), message });
+            // Use try_lock to avoid double panic if called during unwinding.
+            if let Ok(mut s) = stack.try_lock() {
+                s.push(trace);
             }
         });
         Self { id, _phantom: std::marker::PhantomData }
@@ -60,8 +83,8 @@ impl ScopedTraceGuard {
 impl Drop for ScopedTraceGuard {
     fn drop(&mut self) {
         TRACE_STACK.with(|stack| {
-            // Use try_borrow_mut to avoid double panic if called during unwinding.
-            if let Ok(mut s) = stack.try_borrow_mut() {
+            // Use try_lock to avoid double panic if called during unwinding.
+            if let Ok(mut s) = stack.try_lock() {
                 if let Some(pos) = s.iter().rposition(|t| t.id == self.id) {
                     s.remove(pos);
                 }
@@ -72,14 +95,107 @@ impl Drop for ScopedTraceGuard {
 
 /// Retrieves a clone of the current thread's trace stack.
 pub fn get_scoped_traces() -> Vec<TraceInfo> {
-    TRACE_STACK.with(|stack| stack.try_borrow().map(|s| s.clone()).unwrap_or_default())
+    TraceStack::current().traces()
+}
+
+/// Runs `future` with the trace `message`, which is included in every test
+/// failure message generated while it runs, like
+/// [`scoped_trace!`][crate::scoped_trace] does for a synchronous scope.
+///
+/// The traces active where `with_trace` is called are carried along, so they
+/// are also included when the future is polled on another thread, e.g., when
+/// it is spawned on a multi-threaded async runtime:
+///
+/// ```ignore
+/// #[gtest]
+/// #[tokio::test(flavor = "multi_thread")]
+/// async fn fetches_all_pages() {
+///     for page in 0..3 {
+///         tokio::spawn(with_trace(format!("page {page}"), async move {
+///             expect_that!(fetch(page).await, ok(anything()));
+///         }))
+///         .await
+///         .unwrap();
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn with_trace<F: Future>(
+    message: impl Into<String>,
+    future: F,
+) -> impl Future<Output = F::Output> {
+    let caller = std::panic::Location::caller();
+    let trace = TraceInfo {
+        id: NEXT_TRACE_ID.fetch_add(1, Ordering::Relaxed),
+        file: caller.file(),
+        line: caller.line(),
+        message: message.into(),
+    };
+    crate::listener::notify(|listener| listener.on_trace_pushed(&trace));
+    let mut traces = get_scoped_traces();
+    traces.push(trace);
+    WithTrace { traces, future: Box::pin(future) }
+}
+
+/// A future pushing its traces on the trace stack of the thread polling it.
+struct WithTrace<F> {
+    traces: Vec<TraceInfo>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithTrace<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _pushed = PushedTraces::push(&self.traces);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Traces pushed on the current thread's trace stack until this is dropped, by
+/// [`WithTrace`] for the duration of one poll, or by a thread running in the
+/// context of a test.
+pub(crate) struct PushedTraces {
+    ids: Vec<usize>,
+}
+
+impl PushedTraces {
+    /// Pushes the `traces` which are not already on the current thread's trace
+    /// stack, e.g., because the future is polled within the scope in which it
+    /// was created.
+    pub(crate) fn push(traces: &[TraceInfo]) -> Self {
+        let mut ids = vec![];
+        TRACE_STACK.with(|stack| {
+            // Use try_lock to avoid double panic if called during unwinding.
+            if let Ok(mut s) = stack.try_lock() {
+                for trace in traces {
+                    if !s.iter().any(|t| t.id == trace.id) {
+                        ids.push(trace.id);
+                        s.push(trace.clone());
+                    }
+                }
+            }
+        });
+        Self { ids }
+    }
+}
+
+impl Drop for PushedTraces {
+    fn drop(&mut self) {
+        TRACE_STACK.with(|stack| {
+            // Use try_lock to avoid double panic if called during unwinding.
+            if let Ok(mut s) = stack.try_lock() {
+                s.retain(|t| !self.ids.contains(&t.id));
+            }
+        });
+    }
 }
 
 // Test-only state and helpers, hidden from production API.
 #[cfg(test)]
 pub(crate) mod test_helpers {
     use super::*;
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
 
     thread_local! {
         pub static CAPTURED_TRACES_IN_HOOK: RefCell<Vec<TraceInfo>> = const { RefCell::new(Vec::new()) };
@@ -103,6 +219,32 @@ pub(crate) mod test_helpers {
 mod tests {
     use super::test_helpers::*;
     use super::*;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::Thread;
+
+    /// Polls `future` to completion on the current thread.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct ThreadWaker(Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
 
     #[test]
     fn test_scoped_trace_fatal() {
@@ -123,4 +265,41 @@ mod tests {
 
         enable_capture_in_hook(false);
     }
+
+    fn messages(traces: Vec<TraceInfo>) -> Vec<String> {
+        traces.into_iter().map(|trace| trace.message).collect()
+    }
+
+    #[test]
+    fn with_trace_applies_to_future_polled_on_other_thread() {
+        let future = with_trace("async trace", async { get_scoped_traces() });
+
+        let traces = std::thread::spawn(move || block_on(future)).join().unwrap();
+
+        assert_eq!(messages(traces), ["async trace"]);
+        assert!(get_scoped_traces().is_empty());
+    }
+
+    #[test]
+    fn with_trace_carries_enclosing_traces() {
+        let future = {
+            let _guard = ScopedTraceGuard::new("enclosing trace".to_string());
+            with_trace("async trace", async { get_scoped_traces() })
+        };
+
+        let traces = std::thread::spawn(move || block_on(future)).join().unwrap();
+
+        assert_eq!(messages(traces), ["enclosing trace", "async trace"]);
+    }
+
+    #[test]
+    fn with_trace_does_not_duplicate_enclosing_traces() {
+        let _guard = ScopedTraceGuard::new("enclosing trace".to_string());
+        let future = with_trace("async trace", async { get_scoped_traces() });
+
+        let traces = block_on(future);
+
+        assert_eq!(messages(traces), ["enclosing trace", "async trace"]);
+        assert_eq!(messages(get_scoped_traces()), ["enclosing trace"]);
+    }
 }
@@ -0,0 +1,304 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detects the end of the last `#[gtest]` test run by the Rust test harness.
+//!
+//! The Rust test harness offers no hook after its last test. Instead, every
+//! `#[gtest]` test registers itself before `main`, and the tests which the
+//! harness runs are derived from its command line arguments, as parsed by
+//! `HarnessSelection`. The static fixtures and global test environments are
+//! torn down and the test report is written when the last of these tests ends.
+//!
+//! The tests to run cannot be determined if the arguments include one unknown
+//! to this module, or if a test uses rstest, which names its cases itself. The
+//! static fixtures and global test environments are then not torn down, rather
+//! than possibly while tests are still using them.
+
+use crate::internal::test_report;
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// A `#[gtest]` test run by the Rust test harness.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct HarnessTest {
+    pub name: &'static str,
+    /// Whether the test is annotated with `#[ignore]`.
+    pub ignored: bool,
+    /// Whether the test is annotated with `#[should_panic]`.
+    pub should_panic: bool,
+}
+
+/// The tests registered so far.
+static TESTS: Mutex<Vec<HarnessTest>> = Mutex::new(Vec::new());
+
+/// Whether a test which cannot be tracked, as it uses rstest, is registered.
+static HAS_UNTRACKED_TESTS: AtomicBool = AtomicBool::new(false);
+
+/// Registers `test` to wait for before tearing down the static fixtures.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`, which
+/// calls it before `main`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn register_harness_test(test: HarnessTest) {
+    TESTS.lock().unwrap_or_else(PoisonError::into_inner).push(test);
+}
+
+/// Registers a test using rstest, whose cases cannot be told apart from the
+/// other tests by name, so that the static fixtures are never torn down.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`, which
+/// calls it before `main`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn register_untracked_test() {
+    HAS_UNTRACKED_TESTS.store(true, Ordering::Relaxed);
+}
+
+/// Notes the end of the `#[gtest]` test of the given name when dropped.
+///
+/// The static fixtures and global test environments are torn down when the
+/// last test run by the Rust test harness ends, which then also writes the
+/// test report and announces the disabled tests.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct TestEndGuard(pub &'static str);
+
+impl Drop for TestEndGuard {
+    fn drop(&mut self) {
+        let Some(pending) = PENDING_TESTS.get_or_init(pending_tests) else {
+            return;
+        };
+        let last_test_ended = {
+            let mut pending = pending.lock().unwrap_or_else(PoisonError::into_inner);
+            pending.remove(self.0) && pending.is_empty()
+        };
+        if last_test_ended {
+            crate::fixtures::tear_down_static_fixtures();
+            test_report::write_report();
+            let disabled = test_report::disabled_test_count();
+            if disabled > 0 {
+                let noun = if disabled == 1 { "TEST" } else { "TESTS" };
+                let _ = writeln!(std::io::stderr(), "\n  YOU HAVE {disabled} DISABLED {noun}\n");
+            }
+        }
+    }
+}
+
+/// Returns whether the end of the last test run by the Rust test harness can
+/// be detected.
+pub(crate) fn last_test_is_known() -> bool {
+    PENDING_TESTS.get_or_init(pending_tests).is_some()
+}
+
+/// The registered tests which the Rust test harness has yet to finish, known
+/// once the first of them ends, or `None` if they cannot be determined.
+static PENDING_TESTS: OnceLock<Option<Mutex<HashSet<&'static str>>>> = OnceLock::new();
+
+fn pending_tests() -> Option<Mutex<HashSet<&'static str>>> {
+    let tests = TESTS.lock().unwrap_or_else(PoisonError::into_inner);
+    // Without the Rust test harness, no test is registered here.
+    if tests.is_empty() || HAS_UNTRACKED_TESTS.load(Ordering::Relaxed) {
+        return None;
+    }
+    match HarnessSelection::parse(std::env::args().skip(1)) {
+        Ok(selection) => Some(Mutex::new(
+            tests.iter().filter(|test| selection.runs(test)).map(|test| test.name).collect(),
+        )),
+        Err(argument) => {
+            // The output of the test is captured by the test harness.
+            let _ = writeln!(
+                std::io::stderr(),
+                "GoogleTest cannot tell which tests run with the argument {argument}, so \
+                 static fixtures and global test environments are not torn down."
+            );
+            None
+        }
+    }
+}
+
+/// The tests selected by the command line arguments of the Rust test harness.
+#[derive(Debug, Default)]
+struct HarnessSelection {
+    filters: Vec<String>,
+    skip: Vec<String>,
+    exact: bool,
+    ignored: bool,
+    include_ignored: bool,
+    exclude_should_panic: bool,
+}
+
+impl HarnessSelection {
+    /// Parses the arguments `args` of the Rust test harness, returning the
+    /// first argument it does not know as an error.
+    ///
+    /// Options taking a value accept it both as `--option value` and as
+    /// `--option=value`.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut selection = Self::default();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                selection.filters.extend(args);
+                break;
+            }
+            if !arg.starts_with('-') {
+                selection.filters.push(arg);
+                continue;
+            }
+            let (option, value) = match arg.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => {
+                    (option, Some(value.to_string()))
+                }
+                _ => match arg.strip_prefix("-Z") {
+                    Some(value) if !value.is_empty() => ("-Z", Some(value.to_string())),
+                    _ => (arg.as_str(), None),
+                },
+            };
+            match option {
+                "--exact" => selection.exact = true,
+                "--ignored" => selection.ignored = true,
+                "--include-ignored" => selection.include_ignored = true,
+                "--exclude-should-panic" => selection.exclude_should_panic = true,
+                "--bench"
+                | "--ensure-time"
+                | "--fail-fast"
+                | "--force-run-in-process"
+                | "--help"
+                | "-h"
+                | "--list"
+                | "--nocapture"
+                | "--no-capture"
+                | "--quiet"
+                | "-q"
+                | "--report-time"
+                | "--show-output"
+                | "--shuffle"
+                | "--test" => {}
+                "--skip" | "--color" | "--format" | "--logfile" | "--shuffle-seed"
+                | "--test-threads" | "-Z" => {
+                    let Some(value) = value.or_else(|| args.next()) else {
+                        return Err(arg);
+                    };
+                    if option == "--skip" {
+                        selection.skip.push(value);
+                    }
+                    continue;
+                }
+                _ => return Err(arg),
+            }
+            if value.is_some() {
+                return Err(arg);
+            }
+        }
+        Ok(selection)
+    }
+
+    fn runs(&self, test: &HarnessTest) -> bool {
+        // The Rust test harness names tests by their path without the crate.
+        let name = test.name.split_once("::").map_or(test.name, |(_, name)| name);
+        let matches = |filter: &String| {
+            if self.exact {
+                name == filter
+            } else {
+                name.contains(filter.as_str())
+            }
+        };
+        let ignored_selected =
+            if self.ignored { test.ignored } else { self.include_ignored || !test.ignored };
+        ignored_selected
+            && !(self.exclude_should_panic && test.should_panic)
+            && (self.filters.is_empty() || self.filters.iter().any(matches))
+            && !self.skip.iter().any(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn harness_test(name: &'static str, ignored: bool) -> HarnessTest {
+        HarnessTest { name, ignored, should_panic: false }
+    }
+
+    fn selection(args: &[&str]) -> HarnessSelection {
+        HarnessSelection::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn harness_selection_matches_filters_without_crate_name() -> Result<()> {
+        let test = harness_test("my_crate::tests::adds", false);
+
+        verify_that!(selection(&[]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--test-threads", "1", "tests::add"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["my_crate"]).runs(&test), eq(false))?;
+        verify_that!(selection(&["--exact", "tests::add"]).runs(&test), eq(false))?;
+        verify_that!(selection(&["--exact", "tests::adds"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--skip", "add"]).runs(&test), eq(false))?;
+        verify_that!(selection(&["--skip=sub", "--nocapture"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--", "--skip"]).runs(&test), eq(false))
+    }
+
+    #[test]
+    fn harness_selection_reads_values_of_options() -> Result<()> {
+        let test = harness_test("my_crate::tests::adds", false);
+
+        verify_that!(selection(&["--test-threads=1", "adds"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--format", "json", "sub"]).runs(&test), eq(false))?;
+        verify_that!(selection(&["-Z", "unstable-options", "adds"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["-Zunstable-options", "sub"]).runs(&test), eq(false))
+    }
+
+    #[test]
+    fn harness_selection_runs_ignored_tests_only_when_requested() -> Result<()> {
+        let test = harness_test("my_crate::tests::slow", true);
+
+        verify_that!(selection(&[]).runs(&test), eq(false))?;
+        verify_that!(selection(&["--ignored"]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--include-ignored"]).runs(&test), eq(true))?;
+        verify_that!(
+            selection(&["--ignored"]).runs(&harness_test("my_crate::tests::fast", false)),
+            eq(false)
+        )
+    }
+
+    #[test]
+    fn harness_selection_excludes_should_panic_tests_when_requested() -> Result<()> {
+        let test =
+            HarnessTest { name: "my_crate::tests::panics", ignored: false, should_panic: true };
+
+        verify_that!(selection(&[]).runs(&test), eq(true))?;
+        verify_that!(selection(&["--exclude-should-panic"]).runs(&test), eq(false))
+    }
+
+    #[test]
+    fn harness_selection_rejects_unknown_arguments() -> Result<()> {
+        let parse = |args: &[&str]| HarnessSelection::parse(args.iter().map(|arg| arg.to_string()));
+
+        verify_that!(parse(&["--unknown"]), err(eq("--unknown")))?;
+        verify_that!(parse(&["--exact=yes"]), err(eq("--exact=yes")))?;
+        verify_that!(parse(&["--skip"]), err(eq("--skip")))
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for `#[gtest(expect_failure)]`.
+//!
+//! A test expected to fail passes if it fails, through an assertion failure
+//! or an error, and is then reported as an expected failure. A panic fails the
+//! test unless `allow_panic` is given. If the test passes, it fails as having
+//! unexpectedly passed, so that a fix of the known failure is noticed.
+//! Expected failures are announced on the standard error, bypassing the output
+//! capture of the test harness.
+
+use crate::internal::test_outcome::TestFailure;
+use crate::internal::test_report;
+use std::io::Write as _;
+
+/// Turns the `result` of running the test `name`, which is expected to fail,
+/// possibly for the given `reason`, into the result of the test.
+///
+/// `result` is `Err` if the test panicked, which is only caught if the test
+/// allows panics as its expected failure.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn check_expected_failure(
+    name: &'static str,
+    reason: Option<&'static str>,
+    result: std::thread::Result<Result<(), TestFailure>>,
+) -> Result<(), TestFailure> {
+    let reason = reason.map(|reason| format!(": {reason}")).unwrap_or_default();
+    match result {
+        Ok(Ok(())) if test_report::running_test_skipped() => Ok(()),
+        Ok(Ok(())) => {
+            let message = format!("Test unexpectedly passed but is expected to fail{reason}");
+            println!("{message}");
+            test_report::record_returned_error(message);
+            test_report::record_outcome(false);
+            Err(TestFailure)
+        }
+        Ok(Err(_)) | Err(_) => {
+            test_report::record_expected_failure(format!("Expected failure{reason}"));
+            let _ = writeln!(std::io::stderr(), "EXPECTED FAILURE {name}{reason}");
+            Ok(())
+        }
+    }
+}
@@ -1,30 +1,61 @@
 //! Implements the googletest test filtering protocol.
 //!
 //! The Google test filtering protocol consists of the following
-//! environment variable:
+//! environment variables:
 //!
 //! * TESTBRIDGE_TEST_ONLY: string passed from --test_filter
+//! * GTEST_FILTER: the filter of GoogleTest C++, only consulted if
+//!   TESTBRIDGE_TEST_ONLY is not set
 //!
-//! The format of a filter is a ‘,‘-separated list of wildcard
-//! patterns (called the positive patterns) optionally followed by a
-//! ‘-’ and another ‘,‘-separated pattern list (called the negative
-//! patterns). A test matches the filter if and only if it matches any
-//! of the positive patterns but does not match any of the negative
-//! patterns.  (Note that this is a deliberate devation from GTest
-//! C++, which uses colons to separate the patterns, as colons will
-//! unfortunately clash with Rust's "::" namespacing operator.)
+//! The format of a filter is a list of wildcard patterns (called the positive
+//! patterns) optionally followed by a ‘-’ and another list of patterns (called
+//! the negative patterns). A test matches the filter if and only if it matches
+//! any of the positive patterns but does not match any of the negative
+//! patterns.
+//!
+//! The patterns of a list are separated by ‘,’ or, as in GTest C++, by ‘:’. A
+//! ‘:’ which is part of Rust's "::" namespacing operator is not a separator.
+//!
+//! A pattern containing a ‘.’ is in the `Suite.Test` form of GTest C++. Its
+//! dots stand for "::", and it matches a test if it matches its full name or
+//! its name without some of the leading modules. For example, both
+//! "tests.my_test" and "my_crate.tests.*" match the test
+//! "my_crate::tests::my_test". This allows sharing a filter between C++ and
+//! Rust test binaries.
 //!
 //! As an example: "*mount*-*doom*" will accept any string that contains the
 //! substring "mount", as long as it also doesn't contain "doom"
+//!
+//! As in GTest C++, a test whose name or the name of an enclosing module starts
+//! with `DISABLED_` is disabled. It is not run unless the environment variable
+//! GTEST_ALSO_RUN_DISABLED_TESTS is set to a value other than `0`. Each
+//! disabled test is announced on the standard error, followed by their number
+//! after the last test.
 use crate::internal::glob::{is_glob_pattern, Pattern};
 use std::sync::OnceLock;
 
 static TEST_FILTER: OnceLock<Box<dyn TestFilter + Send + Sync>> = OnceLock::new();
 
+static ALSO_RUN_DISABLED_TESTS: OnceLock<bool> = OnceLock::new();
+
+/// Sets the filter given by `--gtest_filter` to the runner of
+/// `googletest::main!()`, taking precedence over the environment variables.
+pub(crate) fn set_filter(filter: &str) {
+    let _ = TEST_FILTER.set(Box::new(get_test_filter(filter)));
+}
+
+/// Requests running the disabled tests, as `--gtest_also_run_disabled_tests`
+/// does for the runner of `googletest::main!()`.
+pub(crate) fn set_also_run_disabled_tests() {
+    let _ = ALSO_RUN_DISABLED_TESTS.set(true);
+}
+
 pub fn test_should_run(test_name: &str) -> bool {
     let test_filter = TEST_FILTER.get_or_init(|| {
         if let Ok(testbridge_test_only) = std::env::var("TESTBRIDGE_TEST_ONLY") {
             Box::new(get_test_filter(&testbridge_test_only))
+        } else if let Ok(gtest_filter) = std::env::var("GTEST_FILTER") {
+            Box::new(get_test_filter(&gtest_filter))
         } else {
             Box::new(AcceptAll)
         }
@@ -33,6 +64,26 @@ pub fn test_should_run(test_name: &str) -> bool {
     test_filter.filter(test_name)
 }
 
+/// Returns whether the test `test_name` is disabled and should not run even
+/// though it matches the filter.
+pub fn test_is_disabled(test_name: &str) -> bool {
+    !also_run_disabled_tests() && has_disabled_prefix(test_name)
+}
+
+/// Returns whether GTEST_ALSO_RUN_DISABLED_TESTS requests running the disabled
+/// tests.
+pub(crate) fn also_run_disabled_tests() -> bool {
+    *ALSO_RUN_DISABLED_TESTS.get_or_init(|| {
+        std::env::var("GTEST_ALSO_RUN_DISABLED_TESTS").is_ok_and(|value| value != "0")
+    })
+}
+
+/// Returns whether the test or any of its enclosing modules has a name starting
+/// with `DISABLED_`.
+fn has_disabled_prefix(test_name: &str) -> bool {
+    test_name.split("::").any(|segment| segment.starts_with("DISABLED_"))
+}
+
 trait TestFilter {
     /// Returns true if the test should run.
     fn filter(&self, test_name: &str) -> bool;
@@ -59,58 +110,108 @@ impl TestFilter for Matches {
     }
 }
 
-struct Collection {
-    // The positive portion:
-    positive_equals: Box<[Equals]>,
-    positive_matches: Box<[Matches]>,
+/// A pattern in the `Suite.Test` form, with its dots replaced by "::". It
+/// matches the full name of a test or any suffix of it which starts at a module
+/// boundary.
+struct SuiteForm(Box<dyn TestFilter + Send + Sync>);
+impl TestFilter for SuiteForm {
+    fn filter(&self, test_name: &str) -> bool {
+        self.0.filter(test_name)
+            || test_name.match_indices("::").any(|(i, _)| self.0.filter(&test_name[i + 2..]))
+    }
+}
 
-    // The negative portion:
-    negative_equals: Box<[Equals]>,
-    negative_matches: Box<[Matches]>,
+/// The positive or the negative portion of a filter.
+#[derive(Default)]
+struct PatternList {
+    equals: Box<[Equals]>,
+    matches: Box<[Matches]>,
+    suite_forms: Box<[SuiteForm]>,
+}
+
+impl PatternList {
+    fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        let (suite_forms, patterns): (Vec<_>, Vec<_>) =
+            patterns.into_iter().partition(|s| s.contains('.'));
+        let (with_globs, literals): (Vec<_>, Vec<_>) =
+            patterns.into_iter().partition(|s| is_glob_pattern(s));
+        PatternList {
+            equals: literals.into_iter().map(|s| Equals(s.to_string())).collect(),
+            matches: with_globs.into_iter().map(|s| Matches(Pattern::new(s.to_string()))).collect(),
+            suite_forms: suite_forms
+                .into_iter()
+                .map(|s| {
+                    let s = s.replace('.', "::");
+                    let filter: Box<dyn TestFilter + Send + Sync> = if is_glob_pattern(&s) {
+                        Box::new(Matches(Pattern::new(s)))
+                    } else {
+                        Box::new(Equals(s))
+                    };
+                    SuiteForm(filter)
+                })
+                .collect(),
+        }
+    }
+
+    fn any_matches(&self, test_name: &str) -> bool {
+        self.equals.iter().any(|f| f.filter(test_name))
+            || self.matches.iter().any(|f| f.filter(test_name))
+            || self.suite_forms.iter().any(|f| f.filter(test_name))
+    }
+}
+
+struct Collection {
+    positive: PatternList,
+    negative: PatternList,
 }
 
 impl TestFilter for Collection {
     fn filter(&self, test_name: &str) -> bool {
-        (self.positive_equals.iter().any(|f| f.filter(test_name))
-            || self.positive_matches.iter().any(|f| f.filter(test_name)))
-            && (!self.negative_equals.iter().any(|f| f.filter(test_name)))
-            && (!self.negative_matches.iter().any(|f| f.filter(test_name)))
+        self.positive.any_matches(test_name) && !self.negative.any_matches(test_name)
     }
 }
 
 fn get_test_filter(testbridge_test_only: &str) -> Collection {
     let positive_negative: Vec<&str> = testbridge_test_only.splitn(2, '-').collect();
 
-    let (positive_with_globs, positive_literals): (Vec<_>, Vec<_>) = {
+    let positive = {
         let positive = positive_negative[0];
         if positive.is_empty() {
             // Forces the empty positive filter to accept everything:
-            (vec!["*"], vec![])
+            PatternList::new(["*"])
         } else {
-            positive.split(',').partition(|s| is_glob_pattern(s))
+            PatternList::new(split_patterns(positive))
         }
     };
 
-    let (negative_with_globs, negative_literals): (Vec<_>, Vec<_>) = match positive_negative.get(1)
-    {
-        Some(negative) if !negative.is_empty() => {
-            negative.split(',').partition(|s| is_glob_pattern(s))
-        }
-        _ => (vec![], vec![]),
+    let negative = match positive_negative.get(1) {
+        Some(negative) if !negative.is_empty() => PatternList::new(split_patterns(negative)),
+        _ => PatternList::default(),
     };
 
-    Collection {
-        positive_equals: positive_literals.into_iter().map(|s| Equals(s.to_string())).collect(),
-        positive_matches: positive_with_globs
-            .into_iter()
-            .map(|s| Matches(Pattern::new(s.to_string())))
-            .collect(),
-        negative_equals: negative_literals.into_iter().map(|s| Equals(s.to_string())).collect(),
-        negative_matches: negative_with_globs
-            .into_iter()
-            .map(|s| Matches(Pattern::new(s.to_string())))
-            .collect(),
+    Collection { positive, negative }
+}
+
+/// Splits a list of patterns at each ‘,’ and at each ‘:’ which is not part of
+/// "::".
+fn split_patterns(patterns: &str) -> Vec<&str> {
+    let bytes = patterns.as_bytes();
+    let mut result = vec![];
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b':' if bytes.get(i + 1) == Some(&b':') => i += 2,
+            b',' | b':' => {
+                result.push(&patterns[start..i]);
+                i += 1;
+                start = i;
+            }
+            _ => i += 1,
+        }
     }
+    result.push(&patterns[start..]);
+    result
 }
 
 #[cfg(test)]
@@ -258,4 +359,80 @@ mod tests {
         verify_that!(filter.filter("fuego"), is_false())?;
         Ok(())
     }
+
+    #[test]
+    fn colon_separates_patterns() -> Result<()> {
+        let filter = get_test_filter("a:b*-bad:c");
+
+        verify_that!(filter.filter("a"), is_true())?;
+        verify_that!(filter.filter("b1"), is_true())?;
+        verify_that!(filter.filter("bad"), is_false())?;
+        verify_that!(filter.filter("c"), is_false())?;
+        Ok(())
+    }
+
+    #[test]
+    fn double_colon_does_not_separate_patterns() -> Result<()> {
+        let filter = get_test_filter("module::test1:other::*-other::bad");
+
+        verify_that!(filter.filter("module::test1"), is_true())?;
+        verify_that!(filter.filter("module::test2"), is_false())?;
+        verify_that!(filter.filter("other::test"), is_true())?;
+        verify_that!(filter.filter("other::bad"), is_false())?;
+        Ok(())
+    }
+
+    #[test]
+    fn split_patterns_at_commas_and_single_colons() -> Result<()> {
+        verify_that!(
+            split_patterns("a::b:c,d::e::f"),
+            elements_are![eq(&"a::b"), eq(&"c"), eq(&"d::e::f")]
+        )
+    }
+
+    #[test]
+    fn suite_form_matches_full_name() -> Result<()> {
+        let filter = get_test_filter("my_crate.tests.my_test");
+
+        verify_that!(filter.filter("my_crate::tests::my_test"), is_true())?;
+        verify_that!(filter.filter("my_crate::tests::other_test"), is_false())?;
+        Ok(())
+    }
+
+    #[test]
+    fn suite_form_matches_name_without_leading_modules() -> Result<()> {
+        let filter = get_test_filter("tests.my_test");
+
+        verify_that!(filter.filter("my_crate::tests::my_test"), is_true())?;
+        verify_that!(filter.filter("my_crate::tests::my_test2"), is_false())?;
+        verify_that!(filter.filter("my_crate::other_tests::my_test"), is_false())?;
+        Ok(())
+    }
+
+    #[test]
+    fn suite_form_with_globs() -> Result<()> {
+        let filter = get_test_filter("FooTest.*:BarTest.Baz-*.Slow*");
+
+        verify_that!(filter.filter("my_crate::FooTest::fast"), is_true())?;
+        verify_that!(filter.filter("my_crate::FooTest::SlowOne"), is_false())?;
+        verify_that!(filter.filter("my_crate::BarTest::Baz"), is_true())?;
+        verify_that!(filter.filter("my_crate::BarTest::Qux"), is_false())?;
+        verify_that!(filter.filter("my_crate::QuxTest::fast"), is_false())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_disabled_prefix_is_disabled() -> Result<()> {
+        verify_that!(has_disabled_prefix("my_crate::tests::DISABLED_my_test"), is_true())
+    }
+
+    #[test]
+    fn test_in_module_with_disabled_prefix_is_disabled() -> Result<()> {
+        verify_that!(has_disabled_prefix("my_crate::DISABLED_tests::my_test"), is_true())
+    }
+
+    #[test]
+    fn test_without_disabled_prefix_is_enabled() -> Result<()> {
+        verify_that!(has_disabled_prefix("my_crate::tests::my_DISABLED_test"), is_false())
+    }
 }
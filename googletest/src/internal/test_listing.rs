@@ -0,0 +1,156 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implements listing the tests instead of running them.
+//!
+//! The listing is requested through the environment variable
+//! GTEST_LIST_TESTS:
+//!
+//! * `1` or `text`: each test prints a line with its fully qualified name, its
+//!   location and the index of the shard on which it runs, e.g.
+//!   `my_crate::tests::my_test src/lib.rs:42 shard=0`.
+//! * `json`: each test prints a JSON object on a single line, e.g.
+//!   `{"name":"my_crate::tests::my_test","file":"src/lib.rs","line":42,"shard":0}`.
+//!
+//! The listing is printed on the standard error, bypassing the output capture
+//! of the test harness, so that it is not interleaved with the output of the
+//! harness. With `text:<path>` or `json:<path>`, it is instead appended to the
+//! file `<path>`, which can be shared by several test binaries.
+//!
+//! Only the tests matching the test filter are listed. The shard index is
+//! computed from GTEST_TOTAL_SHARDS, but tests of all shards are listed.
+
+use crate::internal::test_report::json_string;
+use crate::internal::test_sharding::test_shard_index;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Environment variable requesting the listing of the tests.
+const GTEST_LIST_TESTS: &str = "GTEST_LIST_TESTS";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ListingFormat {
+    Text,
+    Json,
+}
+
+/// How and where the tests are listed.
+#[derive(Debug, PartialEq)]
+struct ListingConfig {
+    format: ListingFormat,
+    /// The file to which the listing is appended, or `None` for the standard
+    /// error.
+    path: Option<PathBuf>,
+}
+
+/// Lists the test `name`, defined at `file`:`line`, if the listing of the
+/// tests is requested.
+///
+/// Returns whether the test was listed, in which case it must not run.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn list_test(name: &str, file: &str, line: u32) -> bool {
+    let Some(ListingConfig { format, path }) = listing_config() else {
+        return false;
+    };
+    let shard = test_shard_index(name);
+    let entry = match format {
+        ListingFormat::Text => format!("{name} {file}:{line} shard={shard}\n"),
+        ListingFormat::Json => format!(
+            "{{\"name\":{},\"file\":{},\"line\":{line},\"shard\":{shard}}}\n",
+            json_string(name),
+            json_string(file)
+        ),
+    };
+    let result = match path {
+        Some(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut listing| listing.write_all(entry.as_bytes())),
+        None => std::io::stderr().lock().write_all(entry.as_bytes()),
+    };
+    if let Err(e) = result {
+        eprintln!("failed to list test {name}: {e}");
+    }
+    true
+}
+
+fn listing_config() -> Option<&'static ListingConfig> {
+    static LISTING_CONFIG: OnceLock<Option<ListingConfig>> = OnceLock::new();
+    LISTING_CONFIG
+        .get_or_init(|| parse_listing_config(std::env::var(GTEST_LIST_TESTS).ok()?.as_str()))
+        .as_ref()
+}
+
+fn parse_listing_config(gtest_list_tests: &str) -> Option<ListingConfig> {
+    let (format, path) = match gtest_list_tests.split_once(':') {
+        Some((format, path)) => (format, Some(PathBuf::from(path))),
+        None => (gtest_list_tests, None),
+    };
+    let format = match format {
+        "" | "0" if path.is_none() => return None,
+        "1" if path.is_none() => ListingFormat::Text,
+        "text" => ListingFormat::Text,
+        "json" => ListingFormat::Json,
+        _ => {
+            eprintln!(
+                "unsupported ${GTEST_LIST_TESTS} value \"{gtest_list_tests}\"; tests are run \
+                 instead of listed"
+            );
+            return None;
+        }
+    };
+    Some(ListingConfig { format, path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn config(format: ListingFormat, path: Option<&str>) -> ListingConfig {
+        ListingConfig { format, path: path.map(PathBuf::from) }
+    }
+
+    #[test]
+    fn parse_listing_config_accepts_text_and_json() -> Result<()> {
+        verify_that!(parse_listing_config("1"), some(eq(&config(ListingFormat::Text, None))))?;
+        verify_that!(parse_listing_config("text"), some(eq(&config(ListingFormat::Text, None))))?;
+        verify_that!(parse_listing_config("json"), some(eq(&config(ListingFormat::Json, None))))
+    }
+
+    #[test]
+    fn parse_listing_config_with_path() -> Result<()> {
+        verify_that!(
+            parse_listing_config("json:out/tests.json"),
+            some(eq(&config(ListingFormat::Json, Some("out/tests.json"))))
+        )
+    }
+
+    #[test]
+    fn parse_listing_config_disables_listing_for_zero() -> Result<()> {
+        verify_that!(parse_listing_config("0"), none())
+    }
+
+    #[test]
+    fn parse_listing_config_ignores_unsupported_value() -> Result<()> {
+        verify_that!(parse_listing_config("yaml"), none())
+    }
+}
@@ -0,0 +1,645 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implements the test runner of `googletest::main!()`, for test targets
+//! built with `harness = false`.
+//!
+//! The tests annotated with `#[gtest]` register themselves before `main`, and
+//! the runner executes them one after the other on the main thread, printing
+//! the `[ RUN      ]` and `[       OK ]` lines of GoogleTest C++. It accepts
+//! the following flags, each of which can also be set through the environment
+//! variable of the same name in upper case, as in GoogleTest C++:
+//!
+//! * `--gtest_filter=<filter>`: runs only the tests matching the filter, see
+//!   [`test_filter`][crate::internal::test_filter].
+//! * `--gtest_also_run_disabled_tests`: also runs the disabled tests and the
+//!   tests annotated with `#[ignore]`.
+//! * `--gtest_repeat=<count>`: runs the tests `<count>` times, or forever if
+//!   `<count>` is negative.
+//! * `--gtest_shuffle`: runs the tests in a random order, determined by
+//!   `--gtest_random_seed=<seed>`, between 1 and 99999. The seed 0, the
+//!   default, stands for a seed derived from the current time. Each repetition
+//!   uses the next seed.
+//! * `--gtest_list_tests`: lists the tests matching the filter instead of
+//!   running them, in the format of GoogleTest C++.
+//! * `--gtest_output=(xml|json)[:<path>]`: writes a report, see
+//!   [`test_report`][crate::internal::test_report].
+//! * `--gtest_color=(yes|no|auto)`: whether the output is colored. With
+//!   `auto`, the default, it is colored as are the diffs in assertion
+//!   failures, depending on NO_COLOR, FORCE_COLOR and whether the standard
+//!   output is a terminal.
+//!
+//! Sharding follows the same environment variables as with the Rust test
+//! harness, see [`test_sharding`][crate::internal::test_sharding].
+
+use crate::internal::test_outcome::TestFailure;
+use crate::internal::{test_filter, test_listing, test_report, test_sharding};
+use crate::listener::{self, Outcome, TestEventListener};
+use crate::matcher_support::summarize_diff;
+use crate::TestInfo;
+use std::any::Any;
+use std::panic::catch_unwind;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The largest seed accepted by `--gtest_random_seed`, as in GoogleTest C++.
+const MAX_RANDOM_SEED: u32 = 99999;
+
+/// A test registered by `#[gtest]` for the runner of `googletest::main!()`.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct RegisteredTest {
+    pub name: &'static str,
+    /// The path of the module in which the test is defined.
+    pub module: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+    /// Whether the test is annotated with `#[ignore]`.
+    pub ignored: bool,
+    /// The text expected in the panic message if the test is annotated with
+    /// `#[should_panic]`, which is empty if any message is expected.
+    pub should_panic: Option<&'static str>,
+    pub run: fn() -> Result<(), TestFailure>,
+}
+
+/// The tests registered so far.
+static TESTS: Mutex<Vec<RegisteredTest>> = Mutex::new(Vec::new());
+
+/// The tests which have their own test attribute or use rstest, and are
+/// therefore only known to the Rust test harness.
+static UNSUPPORTED_TESTS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Whether the tests run with the runner of `googletest::main!()` rather than
+/// with the Rust test harness.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// The outcome of the last test which ended, as notified to the listeners.
+static LAST_OUTCOME: Mutex<Option<(Outcome, Duration)>> = Mutex::new(None);
+
+/// Registers `test` to be run by `googletest::main!()`.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`, which
+/// calls it before `main`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn register_test(test: RegisteredTest) {
+    TESTS.lock().unwrap_or_else(PoisonError::into_inner).push(test);
+}
+
+/// Registers the test `name`, which `googletest::main!()` cannot run, to warn
+/// about it.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`, which
+/// calls it before `main`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn register_unsupported_test(name: &'static str) {
+    UNSUPPORTED_TESTS.lock().unwrap_or_else(PoisonError::into_inner).push(name);
+}
+
+/// Returns whether the tests run with the runner of `googletest::main!()`.
+pub(crate) fn is_running() -> bool {
+    RUNNING.load(Ordering::Relaxed)
+}
+
+/// Runs the registered tests as instructed by the command line arguments,
+/// then tears down the static fixtures and the global test environments.
+///
+/// This is intended only for use by the macro `googletest::main!()`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn run_tests() -> ExitCode {
+    let flags = match Flags::parse(std::env::args().skip(1), |name| std::env::var(name).ok()) {
+        Ok(flags) => flags,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if flags.help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    flags.configure();
+    RUNNING.store(true, Ordering::Relaxed);
+    listener::add_test_event_listener(OutcomeRecorder);
+
+    let mut tests = std::mem::take(&mut *TESTS.lock().unwrap_or_else(PoisonError::into_inner));
+    tests.sort_by_key(|test| (test.file, test.line, test.name));
+    if flags.list_tests {
+        list_tests(&tests);
+        return ExitCode::SUCCESS;
+    }
+
+    let (mut selected, disabled) = select_tests(&tests);
+    let printer = Printer { color: flags.use_color() };
+    warn_about_unsupported_tests(&printer);
+    let mut seed = match flags.random_seed {
+        0 => seed_from_time(),
+        seed => seed,
+    };
+    let mut passed = true;
+    let mut iteration = 0;
+    while flags.repeat < 0 || iteration < flags.repeat {
+        if flags.repeat != 1 {
+            println!("\nRepeating all tests (iteration {}) . . .\n", iteration + 1);
+        }
+        if let Some(filter) = &flags.filter {
+            println!("Note: Google Test filter = {filter}");
+        }
+        if flags.shuffle {
+            println!("Note: Randomizing tests' orders with a seed of {seed} .");
+            shuffle(&mut selected, seed);
+            seed = seed % MAX_RANDOM_SEED + 1;
+        }
+        passed &= run_iteration(&selected, disabled, &printer);
+        test_report::write_report();
+        iteration += 1;
+    }
+    crate::fixtures::tear_down_static_fixtures();
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+const USAGE: &str = "\
+This program contains tests written using GoogleTest for Rust. You can use the
+following command line flags to control its behavior:
+
+  --gtest_filter=POSITIVE_PATTERNS[-NEGATIVE_PATTERNS]
+      Run only the tests whose name matches one of the positive patterns but
+      none of the negative patterns.
+  --gtest_also_run_disabled_tests
+      Run disabled and ignored tests too.
+  --gtest_repeat=COUNT
+      Run the tests repeatedly; use a negative count to repeat forever.
+  --gtest_shuffle
+      Randomize the order of the tests.
+  --gtest_random_seed=SEED
+      Random number seed to use for shuffling the tests (between 1 and 99999,
+      or 0 to use a seed based on the current time).
+  --gtest_list_tests
+      List the names of all tests instead of running them.
+  --gtest_output=(json|xml)[:PATH]
+      Generate a JSON or XML report at the given path.
+  --gtest_color=(yes|no|auto)
+      Enable/disable colored output. The default is auto.";
+
+/// The configuration of the runner, from the command line and the
+/// environment.
+#[derive(Debug, PartialEq)]
+struct Flags {
+    filter: Option<String>,
+    also_run_disabled_tests: bool,
+    repeat: i64,
+    shuffle: bool,
+    random_seed: u32,
+    list_tests: bool,
+    output: Option<String>,
+    color: Color,
+    help: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Color {
+    Yes,
+    No,
+    Auto,
+}
+
+impl Flags {
+    /// Parses the command line arguments `args`, using the environment
+    /// variables given by `env` as defaults.
+    fn parse(
+        args: impl IntoIterator<Item = String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let mut flags = Flags {
+            filter: None,
+            also_run_disabled_tests: false,
+            repeat: 1,
+            shuffle: false,
+            random_seed: 0,
+            list_tests: false,
+            output: None,
+            color: Color::Auto,
+            help: false,
+        };
+        for name in ["repeat", "shuffle", "random_seed", "color"] {
+            if let Some(value) = env(&format!("GTEST_{}", name.to_uppercase())) {
+                flags.set(name, Some(&value))?;
+            }
+        }
+        for arg in args {
+            if matches!(arg.as_str(), "-h" | "--help" | "-?") {
+                flags.help = true;
+            } else if let Some(flag) = arg.strip_prefix("--gtest_") {
+                let (name, value) = match flag.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (flag, None),
+                };
+                flags.set(name, value)?;
+            } else {
+                eprintln!("Ignoring unrecognized argument \"{arg}\".");
+            }
+        }
+        Ok(flags)
+    }
+
+    fn set(&mut self, name: &str, value: Option<&str>) -> Result<(), String> {
+        let invalid = || {
+            format!("Invalid value \"{}\" of the flag --gtest_{name}.", value.unwrap_or_default())
+        };
+        match name {
+            "filter" => self.filter = Some(value.ok_or_else(invalid)?.into()),
+            "also_run_disabled_tests" => {
+                self.also_run_disabled_tests = parse_bool(value).ok_or_else(invalid)?
+            }
+            "repeat" => {
+                self.repeat = value.and_then(|value| value.parse().ok()).ok_or_else(invalid)?
+            }
+            "shuffle" => self.shuffle = parse_bool(value).ok_or_else(invalid)?,
+            "random_seed" => {
+                self.random_seed = value
+                    .and_then(|value| value.parse().ok())
+                    .filter(|seed| *seed <= MAX_RANDOM_SEED)
+                    .ok_or_else(invalid)?
+            }
+            "list_tests" => self.list_tests = parse_bool(value).ok_or_else(invalid)?,
+            "output" => self.output = Some(value.ok_or_else(invalid)?.into()),
+            "color" => {
+                self.color = match value.map(str::to_ascii_lowercase).as_deref() {
+                    Some("yes" | "true" | "t" | "1") => Color::Yes,
+                    Some("no" | "false" | "f" | "0") => Color::No,
+                    Some("auto") => Color::Auto,
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(format!("Unknown flag --gtest_{name}.")),
+        }
+        Ok(())
+    }
+
+    /// Passes the flags read by the modules implementing them, before any
+    /// test runs.
+    fn configure(&self) {
+        if let Some(filter) = &self.filter {
+            test_filter::set_filter(filter);
+        }
+        if self.also_run_disabled_tests {
+            test_filter::set_also_run_disabled_tests();
+        }
+        if let Some(output) = &self.output {
+            test_report::set_output(output);
+        }
+    }
+
+    fn use_color(&self) -> bool {
+        match self.color {
+            Color::Yes => true,
+            Color::No => false,
+            Color::Auto => summarize_diff::stdout_supports_color(),
+        }
+    }
+}
+
+/// Parses the value of a boolean flag, which is true if absent.
+fn parse_bool(value: Option<&str>) -> Option<bool> {
+    match value {
+        None | Some("1" | "true" | "yes") => Some(true),
+        Some("0" | "false" | "no") => Some(false),
+        Some(_) => None,
+    }
+}
+
+fn seed_from_time() -> u32 {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    (millis % u128::from(MAX_RANDOM_SEED)) as u32 + 1
+}
+
+/// Shuffles `tests` with the Fisher-Yates algorithm, drawing from a SplitMix64
+/// generator seeded with `seed`.
+fn shuffle<T>(tests: &mut [T], seed: u32) {
+    let mut state = u64::from(seed);
+    let mut next = || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    for i in (1..tests.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        tests.swap(i, j);
+    }
+}
+
+/// Prints the tests matching the filter, grouped by module, in the format of
+/// `--gtest_list_tests` in GoogleTest C++.
+fn list_tests(tests: &[RegisteredTest]) {
+    let mut modules: Vec<(&str, Vec<&RegisteredTest>)> = vec![];
+    for test in tests.iter().filter(|test| test_filter::test_should_run(test.name)) {
+        match modules.iter_mut().find(|(module, _)| *module == test.module) {
+            Some((_, module_tests)) => module_tests.push(test),
+            None => modules.push((test.module, vec![test])),
+        }
+    }
+    for (module, module_tests) in modules {
+        println!("{module}.");
+        for test in module_tests {
+            let info = TestInfo {
+                full_name: test.name,
+                module_path: test.module,
+                file: test.file,
+                line: test.line,
+                should_panic: test.should_panic.is_some(),
+            };
+            println!("  {}", info.name());
+        }
+    }
+}
+
+/// Warns about the tests which `googletest::main!()` does not run, as they
+/// have another test attribute or use rstest.
+fn warn_about_unsupported_tests(printer: &Printer) {
+    let mut unsupported = UNSUPPORTED_TESTS.lock().unwrap_or_else(PoisonError::into_inner);
+    if unsupported.is_empty() {
+        return;
+    }
+    unsupported.sort();
+    printer.colored(
+        YELLOW,
+        &format!(
+            "WARNING: googletest::main!() does not run {} with another test attribute or \
+            rstest:\n",
+            count(unsupported.len(), "test")
+        ),
+    );
+    for name in unsupported.iter() {
+        printer.colored(YELLOW, &format!("  {name}\n"));
+    }
+}
+
+/// Selects the tests to run, recording the disabled ones, and returns them with
+/// the number of disabled tests.
+fn select_tests(tests: &[RegisteredTest]) -> (Vec<&RegisteredTest>, usize) {
+    let mut selected = vec![];
+    let mut disabled = 0;
+    for test in tests {
+        if !test_filter::test_should_run(test.name) {
+            // Left out of the report, as in GoogleTest C++.
+        } else if test_listing::list_test(test.name, test.file, test.line) {
+            // Listed through GTEST_LIST_TESTS instead of run.
+        } else if test_filter::test_is_disabled(test.name)
+            || test.ignored && !test_filter::also_run_disabled_tests()
+        {
+            test_report::record_disabled_test(test.name, test.file, test.line);
+            disabled += 1;
+        } else if test_sharding::test_should_run(test.name) {
+            selected.push(test);
+        }
+    }
+    (selected, disabled)
+}
+
+/// Runs the `selected` tests and prints a summary, including the number of
+/// `disabled` tests. Returns whether all of them passed.
+fn run_iteration(selected: &[&RegisteredTest], disabled: usize, printer: &Printer) -> bool {
+    let start = Instant::now();
+    printer.line(GREEN, "[==========]", &format!("Running {}.", count(selected.len(), "test")));
+    let (mut passed, mut skipped, mut failed) = (vec![], vec![], vec![]);
+    for &test in selected {
+        printer.line(GREEN, "[ RUN      ]", test.name);
+        let (outcome, duration) = run_test(test);
+        let duration = format!("{} ({} ms)", test.name, duration.as_millis());
+        match outcome {
+            Outcome::Passed | Outcome::ExpectedFailure => {
+                printer.line(GREEN, "[       OK ]", &duration);
+                passed.push(test.name);
+            }
+            Outcome::Skipped => {
+                printer.line(GREEN, "[  SKIPPED ]", &duration);
+                skipped.push(test.name);
+            }
+            Outcome::Failed => {
+                printer.line(RED, "[  FAILED  ]", &duration);
+                failed.push(test.name);
+            }
+        }
+    }
+
+    let ran = passed.len() + skipped.len() + failed.len();
+    printer.line(
+        GREEN,
+        "[==========]",
+        &format!("{} ran. ({} ms total)", count(ran, "test"), start.elapsed().as_millis()),
+    );
+    printer.line(GREEN, "[  PASSED  ]", &format!("{}.", count(passed.len(), "test")));
+    if !skipped.is_empty() {
+        printer.line(
+            GREEN,
+            "[  SKIPPED ]",
+            &format!("{}, listed below:", count(skipped.len(), "test")),
+        );
+        for name in &skipped {
+            printer.line(GREEN, "[  SKIPPED ]", name);
+        }
+    }
+    if !failed.is_empty() {
+        printer.line(
+            RED,
+            "[  FAILED  ]",
+            &format!("{}, listed below:", count(failed.len(), "test")),
+        );
+        for name in &failed {
+            printer.line(RED, "[  FAILED  ]", name);
+        }
+        println!("\n {}", count(failed.len(), "FAILED TEST").to_uppercase());
+    }
+    if disabled > 0 {
+        printer.colored(
+            YELLOW,
+            &format!("  YOU HAVE {}\n", count(disabled, "DISABLED TEST").to_uppercase()),
+        );
+    }
+    failed.is_empty()
+}
+
+/// Runs `test`, returning its outcome and its duration.
+fn run_test(test: &RegisteredTest) -> (Outcome, Duration) {
+    let start = Instant::now();
+    *LAST_OUTCOME.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    let result = catch_unwind(test.run);
+    let recorded = LAST_OUTCOME.lock().unwrap_or_else(PoisonError::into_inner).take();
+    let (mut outcome, duration) = recorded.unwrap_or_else(|| {
+        let outcome = if matches!(result, Ok(Ok(()))) { Outcome::Passed } else { Outcome::Failed };
+        (outcome, start.elapsed())
+    });
+    // As with the Rust test harness, the panic of a `#[should_panic]` test must
+    // contain the expected text.
+    if let (Some(expected), Err(payload)) = (test.should_panic, &result) {
+        let message = panic_message(payload.as_ref());
+        if !message.contains(expected) {
+            println!(
+                "note: panic did not contain expected string\n      panic message: \
+                 {message:?}\n expected substring: {expected:?}"
+            );
+            outcome = Outcome::Failed;
+        }
+    } else if test.should_panic.is_some() && outcome == Outcome::Failed {
+        println!("note: test did not panic as expected");
+    }
+    (outcome, duration)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        ""
+    }
+}
+
+/// Returns e.g. "1 test" or "2 tests".
+fn count(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Records the outcome of each test, as determined by the test report.
+struct OutcomeRecorder;
+
+impl TestEventListener for OutcomeRecorder {
+    fn on_test_end(&self, _: &TestInfo, outcome: Outcome, duration: Duration) {
+        *LAST_OUTCOME.lock().unwrap_or_else(PoisonError::into_inner) = Some((outcome, duration));
+    }
+}
+
+const GREEN: &str = "\x1b[0;32m";
+const RED: &str = "\x1b[0;31m";
+const YELLOW: &str = "\x1b[0;33m";
+const RESET: &str = "\x1b[m";
+
+/// Prints the lines of the runner, colored if requested.
+struct Printer {
+    color: bool,
+}
+
+impl Printer {
+    /// Prints `text` after the `tag`, which is colored.
+    fn line(&self, color: &str, tag: &str, text: &str) {
+        self.colored(color, tag);
+        println!(" {text}");
+    }
+
+    fn colored(&self, color: &str, text: &str) {
+        if self.color {
+            print!("{color}{text}{RESET}");
+        } else {
+            print!("{text}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn parse(args: &[&str], env: &[(&str, &str)]) -> std::result::Result<Flags, String> {
+        Flags::parse(args.iter().map(|arg| arg.to_string()), |name| {
+            env.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn parse_flags_reads_gtest_flags() -> Result<()> {
+        let flags = parse(
+            &[
+                "--gtest_filter=tests::*-*slow*",
+                "--gtest_repeat=3",
+                "--gtest_shuffle",
+                "--gtest_random_seed=42",
+                "--gtest_output=xml:out/",
+                "--gtest_color=no",
+            ],
+            &[],
+        )
+        .unwrap();
+
+        verify_that!(
+            flags,
+            matches_pattern!(Flags {
+                filter: some(eq("tests::*-*slow*")),
+                repeat: eq(&3),
+                shuffle: eq(&true),
+                random_seed: eq(&42),
+                output: some(eq("xml:out/")),
+                color: eq(&Color::No),
+                ..
+            })
+        )
+    }
+
+    #[test]
+    fn parse_flags_takes_defaults_from_environment() -> Result<()> {
+        let flags =
+            parse(&["--gtest_repeat=2"], &[("GTEST_REPEAT", "5"), ("GTEST_SHUFFLE", "1")]).unwrap();
+
+        verify_that!(flags, matches_pattern!(Flags { repeat: eq(&2), shuffle: eq(&true), .. }))
+    }
+
+    #[test]
+    fn parse_flags_rejects_invalid_values() -> Result<()> {
+        verify_that!(
+            parse(&["--gtest_repeat=many"], &[]),
+            err(contains_substring("gtest_repeat"))
+        )?;
+        verify_that!(parse(&["--gtest_random_seed=100000"], &[]), err(anything()))?;
+        verify_that!(parse(&["--gtest_colour=yes"], &[]), err(eq("Unknown flag --gtest_colour.")))
+    }
+
+    #[test]
+    fn parse_flags_ignores_other_arguments() -> Result<()> {
+        verify_that!(parse(&["--nocapture", "my_test"], &[]), ok(anything()))
+    }
+
+    #[test]
+    fn shuffle_depends_only_on_seed() -> Result<()> {
+        let mut first: Vec<u32> = (0..20).collect();
+        let mut second = first.clone();
+        shuffle(&mut first, 7);
+        shuffle(&mut second, 7);
+
+        verify_that!(first, eq(&second))?;
+        verify_that!(first, not(eq(&(0..20).collect::<Vec<_>>())))?;
+        first.sort();
+        verify_that!(first, eq(&(0..20).collect::<Vec<_>>()))
+    }
+}
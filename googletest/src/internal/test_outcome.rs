@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::{RefCell, RefMut};
+use crate::internal::{failure_controls, test_report};
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt::{Debug, Display, Error, Formatter};
-use std::sync::OnceLock;
+use std::io::Write as _;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
 use std::thread_local;
 
 /// The outcome hitherto of running a test.
@@ -30,10 +33,39 @@ pub enum TestOutcome {
     /// The test ran or is currently running and at least one assertion has
     /// failed.
     Failure,
+    /// The test was skipped with [`skip!`][crate::skip] and no assertion has
+    /// failed.
+    Skipped,
 }
 
 thread_local! {
-    static CURRENT_TEST_OUTCOME: RefCell<Option<TestOutcome>> = const { RefCell::new(None) };
+    // Shared so that other threads can record failures in the outcome of the
+    // test, see `TestOutcomeHandle`.
+    static CURRENT_TEST_OUTCOME: RefCell<Arc<Mutex<Option<TestOutcome>>>> = RefCell::default();
+}
+
+/// A handle to the [`TestOutcome`] of the current thread, which another thread
+/// can attach to in order to record its assertion failures there.
+#[derive(Clone)]
+pub(crate) struct TestOutcomeHandle(Arc<Mutex<Option<TestOutcome>>>);
+
+impl TestOutcomeHandle {
+    /// Returns the handle to the test outcome of the current thread.
+    pub(crate) fn current() -> Self {
+        Self(CURRENT_TEST_OUTCOME.with(|current| current.borrow().clone()))
+    }
+
+    /// Returns whether a test is running, that is, whether the outcome has
+    /// been initialized and not yet closed.
+    pub(crate) fn is_running(&self) -> bool {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).is_some()
+    }
+
+    /// Makes the current thread use this test outcome, returning the one it
+    /// used so far.
+    pub(crate) fn attach(self) -> Self {
+        Self(CURRENT_TEST_OUTCOME.with(|current| current.replace(self.0)))
+    }
 }
 
 impl TestOutcome {
@@ -48,6 +80,7 @@ impl TestOutcome {
         INSTALL_HOOK.get_or_init(|| {
             let prev_hook = std::panic::take_hook();
             std::panic::set_hook(Box::new(move |info| {
+                crate::internal::test_report::record_panic(info);
                 let traces = crate::internal::scoped_trace::get_scoped_traces();
                 if !traces.is_empty() {
                     #[cfg(test)]
@@ -89,26 +122,39 @@ impl TestOutcome {
     ///
     /// **For internal use only. API stablility is not guaranteed!**
     #[doc(hidden)]
-    pub fn close_current_test_outcome<E: Display>(
+    pub fn close_current_test_outcome<E: Display + 'static>(
         inner_result: Result<(), E>,
     ) -> Result<(), TestFailure> {
-        TestOutcome::with_current_test_outcome(|mut outcome| {
+        // Only settle the outcome under the lock, so that the failure reporting
+        // and listeners below may themselves inspect the current test.
+        let (outer_result, skipped) = TestOutcome::with_current_test_outcome(|mut outcome| {
+            let skipped = matches!(*outcome, Some(TestOutcome::Skipped));
             let outer_result = match &*outcome {
-                Some(TestOutcome::Success) => match inner_result {
-                    Ok(()) => Ok(()),
-                    Err(_) => Err(TestFailure),
+                Some(TestOutcome::Success | TestOutcome::Skipped) => match inner_result {
+                    Err(ref error) if !is_skip(error, skipped) => Err(TestFailure),
+                    _ => Ok(()),
                 },
                 Some(TestOutcome::Failure) => Err(TestFailure),
                 None => {
                     panic!("No test context found. This indicates a bug in GoogleTest.")
                 }
             };
-            if let Err(fatal_assertion_failure) = inner_result {
-                println!("{fatal_assertion_failure}");
-            }
             *outcome = None;
-            outer_result
-        })
+            (outer_result, skipped)
+        });
+        failure_controls::on_test_end();
+        // The error returned by `skip!` only serves to end the test.
+        if let Some(fatal_assertion_failure) = inner_result.err().filter(|e| !is_skip(e, skipped)) {
+            println!("{fatal_assertion_failure}");
+            let any_failure: &dyn Any = &fatal_assertion_failure;
+            match any_failure.downcast_ref::<TestAssertionFailure>() {
+                Some(failure) => test_report::record_assertion_failure(failure),
+                None => test_report::record_returned_error(fatal_assertion_failure.to_string()),
+            }
+            failure_controls::break_if_requested(&fatal_assertion_failure);
+        }
+        test_report::record_outcome(outer_result.is_ok());
+        outer_result
     }
 
     /// Returns a `Result` corresponding to the outcome of the currently running
@@ -120,12 +166,34 @@ impl TestOutcome {
                 .as_mut()
                 .expect("No test context found. This indicates a bug in GoogleTest.");
             match outcome {
-                TestOutcome::Success => Ok(()),
+                TestOutcome::Success | TestOutcome::Skipped => Ok(()),
                 TestOutcome::Failure => Err(TestAssertionFailure::create("Test failed".into())),
             }
         })
     }
 
+    /// Records that the currently running test is skipped for the given
+    /// `reason`, unless it has already failed.
+    pub(crate) fn skip_current_test(reason: &str) {
+        TestOutcome::ensure_test_context_present();
+        let skipped = TestOutcome::with_current_test_outcome(|mut outcome| {
+            match outcome.as_mut() {
+                Some(outcome @ TestOutcome::Success) => *outcome = TestOutcome::Skipped,
+                Some(TestOutcome::Skipped) => {}
+                Some(TestOutcome::Failure) | None => return false,
+            }
+            true
+        });
+        if skipped {
+            test_report::record_skip(reason);
+            // Announce the skip even though the test harness only shows the
+            // output of failed tests.
+            if let Some(name) = test_report::running_test_name() {
+                let _ = writeln!(std::io::stderr(), "SKIPPED {name}: {reason}");
+            }
+        }
+    }
+
     /// Records that the currently running test has failed.
     fn fail_current_test() {
         TestOutcome::with_current_test_outcome(|mut outcome| {
@@ -140,8 +208,12 @@ impl TestOutcome {
     ///
     /// This is primarily intended for use by assertion macros like
     /// `expect_that!`.
-    fn with_current_test_outcome<T>(action: impl FnOnce(RefMut<Option<TestOutcome>>) -> T) -> T {
-        CURRENT_TEST_OUTCOME.with(|current_test_outcome| action(current_test_outcome.borrow_mut()))
+    fn with_current_test_outcome<T>(
+        action: impl FnOnce(MutexGuard<Option<TestOutcome>>) -> T,
+    ) -> T {
+        let current_test_outcome = TestOutcomeHandle::current().0;
+        let outcome = current_test_outcome.lock().unwrap_or_else(PoisonError::into_inner);
+        action(outcome)
     }
 
     /// Ensure that there is a test context present and panic if there is not.
@@ -155,13 +227,29 @@ impl TestOutcome {
                 "
 No test context found.
  * Did you annotate the test with gtest?
- * Is the assertion running in the original test thread?
+ * Is the assertion running in the original test thread? Use
+   googletest::thread::spawn or googletest::thread::scope to make assertions
+   in other threads.
 ",
             );
         })
     }
 }
 
+/// Returns whether `error` is the error with which `skip!` ends a test, which
+/// has been `skipped` if so.
+///
+/// Only a [`TestAssertionFailure`] tells whether it comes from `skip!`. Any
+/// other error type is converted from it, so any error returned by a skipped
+/// test is taken to be the skip.
+fn is_skip<E: 'static>(error: &E, skipped: bool) -> bool {
+    let error: &dyn Any = error;
+    match error.downcast_ref::<TestAssertionFailure>() {
+        Some(failure) => failure.skip,
+        None => skipped,
+    }
+}
+
 /// A marking struct indicating that a test has failed.
 ///
 /// This exists to implement the [Error][std::error::Error] trait. It displays
@@ -197,7 +285,28 @@ pub struct TestAssertionFailure {
     pub description: String,
     pub custom_message: Option<String>,
     pub traces: Vec<crate::internal::scoped_trace::TraceInfo>,
+    /// The parts of `description`, if the failure comes from a matcher.
+    pub match_failure: Option<Box<MatchFailure>>,
     location: Location,
+    /// Whether this is the error with which `skip!` ends the test, rather than
+    /// an actual failure.
+    skip: bool,
+}
+
+/// The structured content of an assertion failure reported by a matcher.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct MatchFailure {
+    /// The expression which was evaluated to obtain the actual value.
+    pub actual_expr: &'static str,
+    /// The `Debug` representation of the actual value.
+    pub actual: String,
+    /// The description of the matcher, i.e., what was expected.
+    pub matcher_description: String,
+    /// The explanation of why the actual value does not match.
+    pub match_explanation: String,
 }
 
 /// A code location.
@@ -232,6 +341,10 @@ static FAILURE_REPORTER_HOOK: OnceLock<fn(&TestAssertionFailure)> = OnceLock::ne
 /// This is a global static, and it is initialized at most once and returns true
 /// if the initialization was successful.
 /// If the hook is already set, this will do nothing and return false.
+///
+/// Unlike a [`TestEventListener`][crate::listener::TestEventListener], which
+/// should be preferred to observe failures, the hook also lets assertions run
+/// outside of `#[gtest]` tests, for use by external test runners.
 #[doc(hidden)]
 pub fn set_failure_reporter_hook_if_not_set(capture_fn: fn(&TestAssertionFailure)) -> bool {
     FAILURE_REPORTER_HOOK.set(capture_fn).is_ok()
@@ -247,10 +360,27 @@ impl TestAssertionFailure {
             description,
             custom_message: None,
             traces: crate::internal::scoped_trace::get_scoped_traces(),
+            match_failure: None,
             location: Location::Real(std::panic::Location::caller()),
+            skip: false,
         }
     }
 
+    /// Creates the error with which `skip!` ends a test skipped for `reason`.
+    #[track_caller]
+    pub(crate) fn skip(reason: &str) -> Self {
+        Self { skip: true, ..Self::create(format!("Skipped: {reason}")) }
+    }
+
+    /// Attaches the structured content `match_failure` of the failure.
+    ///
+    /// **For internal use only. API stablility is not guaranteed!**
+    #[doc(hidden)]
+    pub fn with_match_failure(mut self, match_failure: MatchFailure) -> Self {
+        self.match_failure = Some(Box::new(match_failure));
+        self
+    }
+
     /// Set `location`` to a fake value.
     ///
     /// **For internal use only. API stablility is not guaranteed!**
@@ -261,11 +391,18 @@ impl TestAssertionFailure {
 
     pub(crate) fn log(&self) {
         println!("{self}");
-        if let Some(capture_fn) = FAILURE_REPORTER_HOOK.get() {
+        let capture_fn = FAILURE_REPORTER_HOOK.get();
+        if capture_fn.is_none() {
+            // Listeners then see the test as failed, and run without holding
+            // the outcome lock.
+            TestOutcome::fail_current_test();
+        }
+        test_report::record_assertion_failure(self);
+        if let Some(capture_fn) = capture_fn {
             capture_fn(self);
             return;
         }
-        TestOutcome::fail_current_test();
+        failure_controls::on_non_fatal_failure(self);
     }
 
     /// Returns the file name of the location.
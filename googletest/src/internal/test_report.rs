@@ -0,0 +1,1258 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implements machine-readable test reports.
+//!
+//! A report is requested through the following environment variables:
+//!
+//! * GTEST_OUTPUT: `xml:<path>` writes a JUnit-compatible XML report to
+//!   `<path>`. `json:<path>` writes one JSON record per test, one per line,
+//!   with the structured content of each assertion failure. If `<path>` ends
+//!   with `/`, the report is written to `<path><test binary name>.xml` (or
+//!   `.json`). A bare `xml` or `json` writes to `test_detail.xml` (or
+//!   `test_detail.json`) in the current directory.
+//! * XML_OUTPUT_FILE: the path of the XML report, as set by Bazel. This is
+//!   only consulted if GTEST_OUTPUT is not set.
+//!
+//! The report is written after each iteration of the runner of
+//! `googletest::main!()`, and under the Rust test harness once the last test
+//! it runs has ended. If the last test cannot be determined, see
+//! [`test_end`][crate::internal::test_end], the report is instead rewritten in
+//! full each time a test completes. It is written to a temporary file first, so
+//! that it is never left incomplete.
+//!
+//! See also <https://google.github.io/googletest/advanced.html#generating-an-xml-report>
+
+use crate::internal::test_outcome::TestAssertionFailure;
+use crate::listener::{self, Outcome};
+use crate::TestInfo;
+use std::cell::{Cell, RefCell};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Environment variable selecting the format and the path of the report.
+const GTEST_OUTPUT: &str = "GTEST_OUTPUT";
+
+/// Environment variable with the path of the XML report, as set by Bazel.
+const XML_OUTPUT_FILE: &str = "XML_OUTPUT_FILE";
+
+/// The base name of the report file when GTEST_OUTPUT does not specify a
+/// path.
+const DEFAULT_OUTPUT_FILE_STEM: &str = "test_detail";
+
+thread_local! {
+    static RUNNING_TEST: RefCell<Option<Arc<Mutex<RunningTest>>>> = const { RefCell::new(None) };
+
+    /// The generation of the running test which this thread was attached to.
+    /// Its failures are ignored once the test has moved on to a later
+    /// generation.
+    static ATTACHED_GENERATION: Cell<u64> = const { Cell::new(0) };
+
+    /// Whether a panic on this thread is expected by `verify_panics!` and is
+    /// therefore not a failure of the running test.
+    static EXPECTING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The records of all tests which have completed so far in this process.
+static COMPLETED_TESTS: Mutex<Vec<TestRecord>> = Mutex::new(Vec::new());
+
+/// The number of tests which were not run because they are disabled.
+static DISABLED_TESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// The format of the report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReportFormat {
+    Xml,
+    Json,
+}
+
+impl ReportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Xml => "xml",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+/// Where and how the report is written.
+#[derive(Debug, PartialEq)]
+struct ReportConfig {
+    format: ReportFormat,
+    path: PathBuf,
+}
+
+/// The final status of a test in the report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TestStatus {
+    Passed,
+    Failed,
+    Skipped,
+    /// The test was not run because its name or module starts with
+    /// `DISABLED_`.
+    Disabled,
+    /// The test failed as expected by `#[gtest(expect_failure)]`.
+    ExpectedFailure,
+}
+
+/// A single failure attached to a test in the report.
+#[derive(Clone, Debug)]
+struct FailureRecord {
+    message: String,
+    file: String,
+    line: u32,
+    /// The failure in structured form, if it comes from an assertion.
+    assertion: Option<TestAssertionFailure>,
+}
+
+impl FailureRecord {
+    fn from_assertion_failure(failure: &TestAssertionFailure) -> Self {
+        Self {
+            message: failure.to_string(),
+            file: failure.file().into(),
+            line: failure.line(),
+            assertion: Some(failure.clone()),
+        }
+    }
+}
+
+/// The state of the test currently running on this thread.
+struct RunningTest {
+    name: &'static str,
+    /// The path of the module in which the test is defined.
+    module: &'static str,
+    file: &'static str,
+    line: u32,
+    should_panic: bool,
+    start: Instant,
+    failures: Vec<FailureRecord>,
+    /// Whether the test passed, once its outcome has been closed.
+    passed: Option<bool>,
+    /// The reason given to `skip!`, if the test was skipped.
+    skip_reason: Option<String>,
+    /// The key/value pairs recorded with `record_property!`.
+    properties: Vec<(String, String)>,
+    /// Describes the expected failure of the test, if it failed as expected.
+    expected_failure: Option<String>,
+    /// Whether a thread of the test stopped at GTEST_MAX_FAILURES_PER_TEST.
+    max_failures_reached: bool,
+    /// Incremented whenever the threads attached to the test so far are
+    /// detached from it.
+    generation: u64,
+}
+
+impl RunningTest {
+    fn info(&self) -> TestInfo {
+        TestInfo {
+            full_name: self.name,
+            module_path: self.module,
+            file: self.file,
+            line: self.line,
+            should_panic: self.should_panic,
+        }
+    }
+}
+
+/// A completed test, as written to the report.
+#[derive(Clone, Debug)]
+struct TestRecord {
+    name: &'static str,
+    file: &'static str,
+    line: u32,
+    status: TestStatus,
+    /// Why the test was not run, if its status is [`TestStatus::Skipped`] or
+    /// [`TestStatus::Disabled`], or why it was expected to fail, if its status
+    /// is [`TestStatus::ExpectedFailure`].
+    skip_reason: Option<String>,
+    properties: Vec<(String, String)>,
+    duration: Duration,
+    failures: Vec<FailureRecord>,
+}
+
+/// Finishes the report entry of the running test when the test ends.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct TestReportGuard(());
+
+impl Drop for TestReportGuard {
+    fn drop(&mut self) {
+        let Some(running) = take_running_test() else {
+            return;
+        };
+        let status = match running.passed {
+            _ if running.expected_failure.is_some() => TestStatus::ExpectedFailure,
+            // A `#[should_panic]` test turns a failure into a panic, which is its
+            // expected outcome. Not failing means that it does not panic.
+            Some(passed) if passed != running.should_panic => match running.skip_reason {
+                Some(_) => TestStatus::Skipped,
+                None => TestStatus::Passed,
+            },
+            Some(_) => TestStatus::Failed,
+            // The test panicked before its outcome could be closed, which is what
+            // a `#[should_panic]` test expects.
+            None if running.should_panic => TestStatus::Passed,
+            None => TestStatus::Failed,
+        };
+        let outcome = match status {
+            TestStatus::Passed => Outcome::Passed,
+            TestStatus::Skipped | TestStatus::Disabled => Outcome::Skipped,
+            TestStatus::ExpectedFailure => Outcome::ExpectedFailure,
+            TestStatus::Failed => Outcome::Failed,
+        };
+        let (info, duration) = (running.info(), running.start.elapsed());
+        complete_test(running, status);
+        listener::notify(|listener| listener.on_test_end(&info, outcome, duration));
+    }
+}
+
+/// Starts the report entry of the test `name`, defined in `module` at
+/// `file`:`line`.
+///
+/// The running test is tracked even if no report is requested, since its
+/// failures are also needed to report retried tests.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn start_test(
+    name: &'static str,
+    module: &'static str,
+    file: &'static str,
+    line: u32,
+    should_panic: bool,
+) -> TestReportGuard {
+    RUNNING_TEST.with(|running_test| {
+        *running_test.borrow_mut() = Some(Arc::new(Mutex::new(RunningTest {
+            name,
+            module,
+            file,
+            line,
+            should_panic,
+            start: Instant::now(),
+            failures: vec![],
+            passed: None,
+            skip_reason: None,
+            properties: vec![],
+            expected_failure: None,
+            max_failures_reached: false,
+            generation: 0,
+        })));
+    });
+    ATTACHED_GENERATION.set(0);
+    if let Some(info) = running_test_info() {
+        listener::notify(|listener| listener.on_test_start(&info));
+    }
+    TestReportGuard(())
+}
+
+/// Records that the test `name`, defined at `file`:`line`, was not run because
+/// it is disabled.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn record_disabled_test(name: &'static str, file: &'static str, line: u32) {
+    DISABLED_TESTS.fetch_add(1, Ordering::Relaxed);
+    // The runner of `googletest::main!()` only prints the number of disabled
+    // tests, as GoogleTest C++ does. The Rust test harness reports them as
+    // passed, so they are announced even though it captures their output.
+    if !crate::internal::test_main::is_running() {
+        let _ = writeln!(std::io::stderr(), "DISABLED {name}");
+    }
+    if report_config().is_some() {
+        push_record(TestRecord {
+            name,
+            file,
+            line,
+            status: TestStatus::Disabled,
+            skip_reason: Some("Disabled".into()),
+            properties: vec![],
+            duration: Duration::ZERO,
+            failures: vec![],
+        });
+    }
+}
+
+/// Returns the number of tests recorded as disabled so far.
+pub(crate) fn disabled_test_count() -> usize {
+    DISABLED_TESTS.load(Ordering::Relaxed)
+}
+
+/// Attaches `failure` to the report entry of the running test, if any, and
+/// notifies the listeners of it.
+pub(crate) fn record_assertion_failure(failure: &TestAssertionFailure) {
+    let mut info = None;
+    with_running_test(|running| {
+        running.failures.push(FailureRecord::from_assertion_failure(failure));
+        info = Some(running.info());
+    });
+    listener::notify(|listener| listener.on_assertion_failure(info.as_ref(), failure));
+}
+
+/// Returns the number of failures recorded so far against the running test,
+/// or 0 if no test is running.
+pub(crate) fn failure_count() -> usize {
+    let mut count = 0;
+    with_running_test(|running| count = running.failures.len());
+    count
+}
+
+/// Records that a thread of the running test stopped at the maximum number of
+/// failures, which ends only that thread.
+pub(crate) fn record_max_failures_reached() {
+    with_running_test(|running| running.max_failures_reached = true);
+}
+
+/// Returns whether a thread of the running test stopped at the maximum number
+/// of failures.
+pub(crate) fn max_failures_reached() -> bool {
+    let mut reached = false;
+    with_running_test(|running| reached = running.max_failures_reached);
+    reached
+}
+
+/// Attaches the error `message` returned by the test function to the report
+/// entry of the running test, if any.
+///
+/// The error carries no location, so the location of the test is used.
+pub(crate) fn record_returned_error(message: String) {
+    with_running_test(|running| {
+        let (file, line) = (running.file.into(), running.line);
+        running.failures.push(FailureRecord { message, file, line, assertion: None });
+    });
+}
+
+/// Attaches the panic described by `info` to the report entry of the running
+/// test, if any.
+pub(crate) fn record_panic(info: &std::panic::PanicHookInfo<'_>) {
+    if EXPECTING_PANIC.with(Cell::get) {
+        return;
+    }
+    with_running_test(|running| {
+        let payload = info.payload();
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+        let (file, line) = match info.location() {
+            Some(location) => (location.file().to_string(), location.line()),
+            None => (running.file.to_string(), running.line),
+        };
+        running.failures.push(FailureRecord {
+            message: format!("panicked: {message}"),
+            file,
+            line,
+            assertion: None,
+        });
+    });
+}
+
+/// Runs `action`, during which panics on the current thread are expected and
+/// not attached to the report entry of the running test.
+pub(crate) fn expecting_panic<R>(action: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            EXPECTING_PANIC.with(|expecting| expecting.set(self.0));
+        }
+    }
+    let _restore = Restore(EXPECTING_PANIC.with(|expecting| expecting.replace(true)));
+    action()
+}
+
+/// Records the outcome of the running test, if any. Its report entry is
+/// completed when the test ends.
+///
+/// The parameter `passed` is whether the test has passed according to its
+/// [`TestOutcome`][crate::internal::test_outcome::TestOutcome] and the value
+/// returned by the test function.
+pub(crate) fn record_outcome(passed: bool) {
+    with_running_test(|running| running.passed = Some(passed));
+}
+
+/// Records the property `key` with the given `value` for the running test,
+/// replacing any earlier value of `key`.
+///
+/// This is intended only for use by the macro `record_property!`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn record_property(key: &str, value: impl std::fmt::Display) {
+    crate::internal::test_outcome::TestOutcome::ensure_test_context_present();
+    let value = value.to_string();
+    println!("Property {key}: {value}");
+    with_running_test(|running| {
+        match running.properties.iter_mut().find(|(existing, _)| existing == key) {
+            Some((_, existing_value)) => *existing_value = value,
+            None => running.properties.push((key.into(), value)),
+        }
+    });
+}
+
+/// Records that the running test, if any, was skipped for the given `reason`.
+pub(crate) fn record_skip(reason: &str) {
+    with_running_test(|running| running.skip_reason = Some(reason.into()));
+}
+
+/// Returns whether the running test has been skipped.
+pub(crate) fn running_test_skipped() -> bool {
+    let mut skipped = false;
+    with_running_test(|running| skipped = running.skip_reason.is_some());
+    skipped
+}
+
+/// Records that the running test failed as expected, as described by
+/// `description`.
+pub(crate) fn record_expected_failure(description: String) {
+    with_running_test(|running| running.expected_failure = Some(description));
+}
+
+/// Returns the name of the running test, if any.
+pub(crate) fn running_test_name() -> Option<&'static str> {
+    let mut name = None;
+    with_running_test(|running| name = Some(running.name));
+    name
+}
+
+/// Returns the path of the module in which the running test is defined.
+pub(crate) fn running_test_module() -> Option<&'static str> {
+    let mut module = None;
+    with_running_test(|running| module = Some(running.module));
+    module
+}
+
+/// Returns the information about the running test exposed by
+/// [`TestInfo::current`].
+pub(crate) fn running_test_info() -> Option<TestInfo> {
+    let mut info = None;
+    with_running_test(|running| info = Some(running.info()));
+    info
+}
+
+/// Returns the path of the parent of the module `path`.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn parent_module(path: &'static str) -> &'static str {
+    path.rsplit_once("::").map_or(path, |(parent, _)| parent)
+}
+
+/// Removes the failures recorded so far for the running test, and its outcome,
+/// so that it can be run again. Returns the messages of the removed failures.
+pub(crate) fn take_failures() -> Vec<String> {
+    let mut failures = vec![];
+    with_running_test(|running| {
+        running.passed = None;
+        running.skip_reason = None;
+        failures = running.failures.drain(..).map(|failure| failure.message).collect();
+    });
+    failures
+}
+
+/// A handle to the report entry of the test running on a thread, through which
+/// another thread can contribute to it.
+#[derive(Clone)]
+pub(crate) struct RunningTestHandle(Option<Arc<Mutex<RunningTest>>>, u64);
+
+impl RunningTestHandle {
+    /// Returns the handle to the test running on the current thread, if any.
+    pub(crate) fn current() -> Self {
+        Self(
+            RUNNING_TEST.with(|running_test| running_test.borrow().clone()),
+            ATTACHED_GENERATION.get(),
+        )
+    }
+
+    /// Makes the current thread record its failures in the report entry of
+    /// this test, returning the handle to the test it recorded them for so
+    /// far.
+    pub(crate) fn attach(self) -> Self {
+        Self(
+            RUNNING_TEST.with(|running_test| running_test.replace(self.0)),
+            ATTACHED_GENERATION.replace(self.1),
+        )
+    }
+}
+
+/// Detaches all other threads attached to the test running on the current
+/// thread, so that their failures are no longer recorded against it.
+///
+/// Threads spawned through handles obtained before this call are detached as
+/// well.
+pub(crate) fn detach_other_threads() {
+    with_running_test(|running| {
+        running.generation += 1;
+        ATTACHED_GENERATION.set(running.generation);
+    });
+}
+
+fn with_running_test(action: impl FnOnce(&mut RunningTest)) {
+    RUNNING_TEST.with(|running_test| {
+        // Use try_borrow and try_lock to avoid double panic if called during
+        // unwinding. Otherwise, wait for other threads of the test recording
+        // their failures.
+        let Ok(running_test) = running_test.try_borrow() else {
+            return;
+        };
+        let Some(running_test) = running_test.as_ref() else {
+            return;
+        };
+        let running = if std::thread::panicking() {
+            running_test.try_lock().ok()
+        } else {
+            Some(running_test.lock().unwrap_or_else(PoisonError::into_inner))
+        };
+        if let Some(mut running) = running {
+            // Ignore the threads which were detached from the test.
+            if running.generation == ATTACHED_GENERATION.get() {
+                action(&mut running);
+            }
+        }
+    })
+}
+
+fn take_running_test() -> Option<RunningTest> {
+    let running = RUNNING_TEST.with(|running_test| running_test.try_borrow_mut().ok()?.take())?;
+    let mut running = running.lock().unwrap_or_else(|e| e.into_inner());
+    let failures = std::mem::take(&mut running.failures);
+    let skip_reason = running.skip_reason.take();
+    let properties = std::mem::take(&mut running.properties);
+    let expected_failure = running.expected_failure.take();
+    Some(RunningTest { failures, skip_reason, properties, expected_failure, ..*running })
+}
+
+fn complete_test(running: RunningTest, status: TestStatus) {
+    let RunningTest {
+        name,
+        file,
+        line,
+        start,
+        failures,
+        skip_reason,
+        properties,
+        expected_failure,
+        ..
+    } = running;
+    push_record(TestRecord {
+        name,
+        file,
+        line,
+        status,
+        skip_reason: skip_reason.or(expected_failure),
+        properties,
+        duration: start.elapsed(),
+        failures,
+    });
+}
+
+fn push_record(record: TestRecord) {
+    if report_config().is_none() {
+        return;
+    }
+    COMPLETED_TESTS.lock().unwrap_or_else(|e| e.into_inner()).push(record);
+    let written_at_end =
+        crate::internal::test_main::is_running() || crate::internal::test_end::last_test_is_known();
+    if !written_at_end {
+        write_report();
+    }
+}
+
+/// Writes the report of the tests completed so far, if one is requested.
+pub(crate) fn write_report() {
+    let Some(ReportConfig { format, path }) = report_config() else {
+        return;
+    };
+    // The lock also keeps concurrent writes of the report apart.
+    let completed = COMPLETED_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let content = match format {
+        ReportFormat::Xml => render_xml(&completed),
+        ReportFormat::Json => render_json(&completed),
+    };
+    if let Err(e) = write_report_file(path, &content) {
+        eprintln!("failed to write test report {}: {}", path.display(), e);
+    }
+}
+
+fn write_report_file(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(format!(".{}.tmp", std::process::id()));
+    fs::write(&temporary_path, content)?;
+    fs::rename(&temporary_path, path)
+}
+
+static REPORT_CONFIG: OnceLock<Option<ReportConfig>> = OnceLock::new();
+
+/// Sets the report requested by `--gtest_output` to the runner of
+/// `googletest::main!()`, taking precedence over the environment variables.
+pub(crate) fn set_output(output: &str) {
+    let _ = REPORT_CONFIG.set(parse_report_config(
+        Some(output),
+        std::env::var_os(XML_OUTPUT_FILE).map(PathBuf::from),
+    ));
+}
+
+fn report_config() -> Option<&'static ReportConfig> {
+    REPORT_CONFIG
+        .get_or_init(|| {
+            parse_report_config(
+                std::env::var(GTEST_OUTPUT).ok().as_deref(),
+                std::env::var_os(XML_OUTPUT_FILE).map(PathBuf::from),
+            )
+        })
+        .as_ref()
+}
+
+/// Determines the format and path of the report from the values of
+/// GTEST_OUTPUT and XML_OUTPUT_FILE.
+fn parse_report_config(
+    gtest_output: Option<&str>,
+    xml_output_file: Option<PathBuf>,
+) -> Option<ReportConfig> {
+    let Some(gtest_output) = gtest_output else {
+        return xml_output_file.map(|path| ReportConfig { format: ReportFormat::Xml, path });
+    };
+    let (format, path) = gtest_output.split_once(':').unwrap_or((gtest_output, ""));
+    let format = match format {
+        "xml" => ReportFormat::Xml,
+        "json" => ReportFormat::Json,
+        _ => {
+            eprintln!("unsupported $GTEST_OUTPUT format \"{format}\"; no report is written");
+            return None;
+        }
+    };
+    let path = if path.is_empty() {
+        PathBuf::from(format!("{DEFAULT_OUTPUT_FILE_STEM}.{}", format.extension()))
+    } else if path.ends_with('/') || path.ends_with(std::path::MAIN_SEPARATOR) {
+        Path::new(path).join(format!("{}.{}", binary_name(), format.extension()))
+    } else {
+        PathBuf::from(path)
+    };
+    Some(ReportConfig { format, path })
+}
+
+fn binary_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| DEFAULT_OUTPUT_FILE_STEM.into())
+}
+
+/// Renders `records` as a JUnit-compatible XML document.
+///
+/// Tests are grouped into one `testsuite` per module, and sorted by name so
+/// that the output does not depend on the order in which tests completed.
+fn render_xml(records: &[TestRecord]) -> String {
+    let mut records: Vec<&TestRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.name);
+
+    let mut suites: Vec<(&str, Vec<&TestRecord>)> = vec![];
+    for record in records {
+        let (suite, _) = split_test_name(record.name);
+        match suites.last_mut() {
+            Some((last_suite, suite_records)) if *last_suite == suite => suite_records.push(record),
+            _ => suites.push((suite, vec![record])),
+        }
+    }
+
+    let all: Vec<&TestRecord> = suites.iter().flat_map(|(_, records)| records).copied().collect();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<testsuites {} name=\"AllTests\">", summary_attributes(&all)).unwrap();
+    for (suite, records) in &suites {
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\" {}>",
+            escape_xml_attribute(suite),
+            summary_attributes(records)
+        )
+        .unwrap();
+        for record in records {
+            render_test_case(&mut xml, record);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn render_test_case(xml: &mut String, record: &TestRecord) {
+    let (suite, name) = split_test_name(record.name);
+    let (status, result) = match record.status {
+        TestStatus::Passed | TestStatus::Failed | TestStatus::ExpectedFailure => {
+            ("run", "completed")
+        }
+        TestStatus::Skipped => ("notrun", "skipped"),
+        TestStatus::Disabled => ("notrun", "suppressed"),
+    };
+    write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\" status=\"{status}\" \
+         result=\"{result}\" time=\"{}\"",
+        escape_xml_attribute(name),
+        escape_xml_attribute(suite),
+        escape_xml_attribute(record.file),
+        record.line,
+        format_seconds(record.duration),
+    )
+    .unwrap();
+    if record.status == TestStatus::Passed && record.properties.is_empty() {
+        xml.push_str(" />\n");
+        return;
+    }
+    xml.push_str(">\n");
+    if !record.properties.is_empty() {
+        xml.push_str("      <properties>\n");
+        for (key, value) in &record.properties {
+            writeln!(
+                xml,
+                "        <property name=\"{}\" value=\"{}\" />",
+                escape_xml_attribute(key),
+                escape_xml_attribute(value)
+            )
+            .unwrap();
+        }
+        xml.push_str("      </properties>\n");
+    }
+    if matches!(
+        record.status,
+        TestStatus::Skipped | TestStatus::Disabled | TestStatus::ExpectedFailure
+    ) {
+        // JUnit has no notion of expected failures. As pytest does, they are
+        // reported as skipped, without their failures.
+        writeln!(
+            xml,
+            "      <skipped message=\"{}\" />",
+            escape_xml_attribute(record.skip_reason.as_deref().unwrap_or_default())
+        )
+        .unwrap();
+        if record.status == TestStatus::ExpectedFailure {
+            xml.push_str("    </testcase>\n");
+            return;
+        }
+    } else if record.status == TestStatus::Failed && record.failures.is_empty() {
+        // A `#[should_panic]` test which did not panic has no failure of its own.
+        xml.push_str("      <failure message=\"Test did not panic as expected\" type=\"\" />\n");
+    }
+    for failure in &record.failures {
+        let text = format!("{}:{}\n{}", failure.file, failure.line, failure.message);
+        writeln!(
+            xml,
+            "      <failure message=\"{}\" type=\"\"><![CDATA[{}]]></failure>",
+            escape_xml_attribute(&text),
+            escape_cdata(&text)
+        )
+        .unwrap();
+    }
+    xml.push_str("    </testcase>\n");
+}
+
+/// Renders `records` as JSON Lines, with one JSON object per test.
+///
+/// As with [`render_xml`], tests are sorted by name.
+fn render_json(records: &[TestRecord]) -> String {
+    let mut records: Vec<&TestRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.name);
+
+    let mut json = String::new();
+    for record in records {
+        let (suite, name) = split_test_name(record.name);
+        let status = match record.status {
+            TestStatus::Passed => "passed",
+            TestStatus::Failed => "failed",
+            TestStatus::Skipped => "skipped",
+            TestStatus::Disabled => "disabled",
+            TestStatus::ExpectedFailure => "expected_failure",
+        };
+        let skip_reason = match &record.skip_reason {
+            Some(reason) => format!("\"skip_reason\":{},", json_string(reason)),
+            None => String::new(),
+        };
+        let properties = if record.properties.is_empty() {
+            String::new()
+        } else {
+            let properties: Vec<String> = record
+                .properties
+                .iter()
+                .map(|(key, value)| format!("{}:{}", json_string(key), json_string(value)))
+                .collect();
+            format!("\"properties\":{{{}}},", properties.join(","))
+        };
+        let failures: Vec<String> = record.failures.iter().map(render_json_failure).collect();
+        writeln!(
+            json,
+            "{{\"name\":{},\"suite\":{},\"test\":{},\"file\":{},\"line\":{},\"status\":\"{status}\",\
+             {skip_reason}{properties}\"duration_seconds\":{},\"failures\":[{}]}}",
+            json_string(record.name),
+            json_string(suite),
+            json_string(name),
+            json_string(record.file),
+            record.line,
+            format_seconds(record.duration),
+            failures.join(","),
+        )
+        .unwrap();
+    }
+    json
+}
+
+fn render_json_failure(failure: &FailureRecord) -> String {
+    let assertion = failure.assertion.as_ref();
+    let match_failure = assertion.and_then(|assertion| assertion.match_failure.as_ref());
+    let traces: Vec<String> = assertion
+        .map(|assertion| {
+            assertion
+                .traces
+                .iter()
+                .map(|trace| {
+                    format!(
+                        "{{\"file\":{},\"line\":{},\"message\":{}}}",
+                        json_string(trace.file),
+                        trace.line,
+                        json_string(&trace.message)
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    format!(
+        "{{\"file\":{},\"line\":{},\"message\":{},\"description\":{},\"actual_expr\":{},\
+         \"actual\":{},\"matcher_description\":{},\"match_explanation\":{},\
+         \"custom_message\":{},\"traces\":[{}]}}",
+        json_string(&failure.file),
+        failure.line,
+        json_string(&failure.message),
+        json_optional_string(assertion.map(|assertion| assertion.description.as_str())),
+        json_optional_string(match_failure.map(|match_failure| match_failure.actual_expr)),
+        json_optional_string(match_failure.map(|match_failure| match_failure.actual.as_str())),
+        json_optional_string(
+            match_failure.map(|match_failure| match_failure.matcher_description.as_str())
+        ),
+        json_optional_string(
+            match_failure.map(|match_failure| match_failure.match_explanation.as_str())
+        ),
+        json_optional_string(assertion.and_then(|assertion| assertion.custom_message.as_deref())),
+        traces.join(","),
+    )
+}
+
+fn summary_attributes(records: &[&TestRecord]) -> String {
+    let count = |status| records.iter().filter(|record| record.status == status).count();
+    // JUnit has no notion of disabled tests or expected failures, so they count
+    // as skipped.
+    let duration: Duration = records.iter().map(|record| record.duration).sum();
+    format!(
+        "tests=\"{}\" failures=\"{}\" skipped=\"{}\" errors=\"0\" time=\"{}\"",
+        records.len(),
+        count(TestStatus::Failed),
+        count(TestStatus::Skipped)
+            + count(TestStatus::Disabled)
+            + count(TestStatus::ExpectedFailure),
+        format_seconds(duration)
+    )
+}
+
+/// Splits a fully qualified test name into its module path and function name.
+fn split_test_name(name: &str) -> (&str, &str) {
+    name.rsplit_once("::").unwrap_or(("", name))
+}
+
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Renders `text` as a JSON string literal.
+pub(crate) fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_optional_string(text: Option<&str>) -> String {
+    text.map(json_string).unwrap_or_else(|| "null".into())
+}
+
+/// Escapes `text` for use in a double-quoted XML attribute.
+fn escape_xml_attribute(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#x0A;"),
+            '\r' => escaped.push_str("&#x0D;"),
+            '\t' => escaped.push_str("&#x09;"),
+            c if is_valid_xml_char(c) => escaped.push(c),
+            _ => {}
+        }
+    }
+    escaped
+}
+
+/// Prepares `text` for use in a CDATA section.
+///
+/// The sequence `]]>` cannot appear in a CDATA section, so it is split across
+/// two sections.
+fn escape_cdata(text: &str) -> String {
+    text.chars()
+        .filter(|c| is_valid_xml_char(*c))
+        .collect::<String>()
+        .replace("]]>", "]]]]><![CDATA[>")
+}
+
+/// Returns whether `c` may appear in an XML 1.0 document.
+///
+/// This drops, in particular, the escape characters of colored output.
+fn is_valid_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn record(name: &'static str, status: TestStatus, failures: Vec<FailureRecord>) -> TestRecord {
+        TestRecord {
+            name,
+            file: "src/lib.rs",
+            line: 10,
+            status,
+            skip_reason: match status {
+                TestStatus::Skipped => Some("Not applicable".into()),
+                TestStatus::Disabled => Some("Disabled".into()),
+                _ => None,
+            },
+            properties: vec![],
+            duration: Duration::from_millis(1500),
+            failures,
+        }
+    }
+
+    fn config(format: ReportFormat, path: impl Into<PathBuf>) -> ReportConfig {
+        ReportConfig { format, path: path.into() }
+    }
+
+    #[test]
+    fn report_config_from_gtest_output() -> Result<()> {
+        verify_that!(
+            parse_report_config(Some("xml:out/report.xml"), None),
+            some(eq(&config(ReportFormat::Xml, "out/report.xml")))
+        )
+    }
+
+    #[test]
+    fn report_config_defaults_without_path() -> Result<()> {
+        verify_that!(
+            parse_report_config(Some("xml"), None),
+            some(eq(&config(ReportFormat::Xml, "test_detail.xml")))
+        )
+    }
+
+    #[test]
+    fn report_config_in_directory_is_named_after_binary() -> Result<()> {
+        verify_that!(
+            parse_report_config(Some("xml:out/"), None),
+            some(eq(&config(
+                ReportFormat::Xml,
+                Path::new("out").join(format!("{}.xml", binary_name()))
+            )))
+        )
+    }
+
+    #[test]
+    fn report_config_falls_back_to_xml_output_file() -> Result<()> {
+        verify_that!(
+            parse_report_config(None, Some("bazel.xml".into())),
+            some(eq(&config(ReportFormat::Xml, "bazel.xml")))
+        )
+    }
+
+    #[test]
+    fn report_config_ignores_unsupported_format() -> Result<()> {
+        verify_that!(parse_report_config(Some("yaml:out.yaml"), Some("bazel.xml".into())), none())
+    }
+
+    #[test]
+    fn report_config_for_json() -> Result<()> {
+        verify_that!(
+            parse_report_config(Some("json:out/report.json"), Some("bazel.xml".into())),
+            some(eq(&config(ReportFormat::Json, "out/report.json")))
+        )
+    }
+
+    #[test]
+    fn report_config_for_json_defaults_without_path() -> Result<()> {
+        verify_that!(
+            parse_report_config(Some("json"), None),
+            some(eq(&config(ReportFormat::Json, "test_detail.json")))
+        )
+    }
+
+    #[test]
+    fn render_xml_groups_tests_by_module() -> Result<()> {
+        let xml = render_xml(&[
+            record("a::b::second", TestStatus::Passed, vec![]),
+            record("c::third", TestStatus::Skipped, vec![]),
+            record("a::b::first", TestStatus::Passed, vec![]),
+        ]);
+
+        verify_that!(
+            xml,
+            eq(indoc::indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites tests="3" failures="0" skipped="1" errors="0" time="4.500" name="AllTests">
+                  <testsuite name="a::b" tests="2" failures="0" skipped="0" errors="0" time="3.000">
+                    <testcase name="first" classname="a::b" file="src/lib.rs" line="10" status="run" result="completed" time="1.500" />
+                    <testcase name="second" classname="a::b" file="src/lib.rs" line="10" status="run" result="completed" time="1.500" />
+                  </testsuite>
+                  <testsuite name="c" tests="1" failures="0" skipped="1" errors="0" time="1.500">
+                    <testcase name="third" classname="c" file="src/lib.rs" line="10" status="notrun" result="skipped" time="1.500">
+                      <skipped message="Not applicable" />
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+                "#
+            ))
+        )
+    }
+
+    #[test]
+    fn render_xml_reports_disabled_test_as_suppressed() -> Result<()> {
+        let xml = render_xml(&[record("a::DISABLED_test", TestStatus::Disabled, vec![])]);
+
+        verify_that!(
+            xml,
+            all!(
+                contains_substring(r#"tests="1" failures="0" skipped="1""#),
+                contains_substring(r#"status="notrun" result="suppressed""#),
+                contains_substring(r#"<skipped message="Disabled" />"#),
+            )
+        )
+    }
+
+    #[test]
+    fn render_xml_reports_expected_failure_as_skipped_without_failures() -> Result<()> {
+        let failure = FailureRecord {
+            message: "Expected: 2".into(),
+            file: "src/lib.rs".into(),
+            line: 12,
+            assertion: None,
+        };
+        let mut expected_failure =
+            record("a::known_bug", TestStatus::ExpectedFailure, vec![failure]);
+        expected_failure.skip_reason = Some("Expected failure: bug 123".into());
+
+        verify_that!(
+            render_xml(&[expected_failure]),
+            all!(
+                contains_substring(r#"tests="1" failures="0" skipped="1""#),
+                contains_substring(r#"status="run" result="completed""#),
+                contains_substring(r#"<skipped message="Expected failure: bug 123" />"#),
+                not(contains_substring("<failure")),
+            )
+        )
+    }
+
+    #[test]
+    fn render_xml_includes_properties() -> Result<()> {
+        let mut passed = record("a::test", TestStatus::Passed, vec![]);
+        passed.properties = vec![("seed".into(), "42".into()), ("input".into(), "<a>.txt".into())];
+
+        verify_that!(
+            render_xml(&[passed]),
+            contains_regex(
+                r#"result="completed" time="1.500">\s*<properties>\s*<property name="seed" value="42" />\s*<property name="input" value="&lt;a&gt;.txt" />\s*</properties>\s*</testcase>"#
+            )
+        )
+    }
+
+    #[test]
+    fn render_xml_includes_failure_text_and_location() -> Result<()> {
+        let failure = FailureRecord {
+            message: "Expected: <1> & \"2\"\n]]>".into(),
+            file: "src/lib.rs".into(),
+            line: 12,
+            assertion: None,
+        };
+
+        let xml = render_xml(&[record("a::failing", TestStatus::Failed, vec![failure])]);
+
+        verify_that!(
+            xml,
+            contains_substring(
+                "<failure message=\"src/lib.rs:12&#x0A;Expected: &lt;1&gt; &amp; &quot;2&quot;&#x0A;]]&gt;\" \
+                 type=\"\"><![CDATA[src/lib.rs:12\nExpected: <1> & \"2\"\n]]]]><![CDATA[>]]></failure>"
+            )
+        )
+    }
+
+    #[test]
+    fn render_xml_drops_invalid_characters() -> Result<()> {
+        let failure = FailureRecord {
+            message: "\x1B[1mbold\x1B[0m".into(),
+            file: "src/lib.rs".into(),
+            line: 12,
+            assertion: None,
+        };
+
+        let xml = render_xml(&[record("a::failing", TestStatus::Failed, vec![failure])]);
+
+        verify_that!(xml, not(contains_substring("\x1B")))
+    }
+
+    #[test]
+    fn assertion_failure_is_recorded_with_its_location() -> Result<()> {
+        let failure = TestAssertionFailure::create("Expected something".into()).with_fake_location(
+            "src/other.rs",
+            42,
+            1,
+        );
+
+        let record = FailureRecord::from_assertion_failure(&failure);
+
+        verify_that!(
+            record,
+            matches_pattern!(FailureRecord {
+                message: starts_with("Expected something\n"),
+                file: eq("src/other.rs"),
+                line: eq(&42),
+                ..
+            })
+        )
+    }
+
+    #[test]
+    fn render_json_writes_one_record_per_test() -> Result<()> {
+        let json = render_json(&[
+            record("a::b::second", TestStatus::Failed, vec![]),
+            record("a::first", TestStatus::Passed, vec![]),
+        ]);
+
+        verify_that!(
+            json,
+            eq(indoc::indoc!(
+                r#"
+                {"name":"a::b::second","suite":"a::b","test":"second","file":"src/lib.rs","line":10,"status":"failed","duration_seconds":1.500,"failures":[]}
+                {"name":"a::first","suite":"a","test":"first","file":"src/lib.rs","line":10,"status":"passed","duration_seconds":1.500,"failures":[]}
+                "#
+            ))
+        )
+    }
+
+    #[test]
+    fn render_json_includes_properties() -> Result<()> {
+        let mut passed = record("a::test", TestStatus::Passed, vec![]);
+        passed.properties = vec![("seed".into(), "42".into()), ("latency".into(), "1 ms".into())];
+
+        verify_that!(
+            render_json(&[passed]),
+            contains_substring(
+                r#""status":"passed","properties":{"seed":"42","latency":"1 ms"},"duration_seconds""#
+            )
+        )
+    }
+
+    #[test]
+    fn render_json_includes_structured_assertion_failure() -> Result<()> {
+        let mut failure = verify_that!(1, eq(2))
+            .failure_message("Custom")
+            .unwrap_err()
+            .with_fake_location("src/lib.rs", 12, 1);
+        failure.traces.clear();
+
+        let json = render_json(&[record(
+            "a::failing",
+            TestStatus::Failed,
+            vec![FailureRecord::from_assertion_failure(&failure)],
+        )]);
+
+        verify_that!(
+            json,
+            contains_substring(
+                r#""failures":[{"file":"src/lib.rs","line":12,"message":"Value of: 1\nExpected: is equal to 2\nActual: 1,\n  which isn't equal to 2\nCustom\n  at src/lib.rs:12:1\n","description":"Value of: 1\nExpected: is equal to 2\nActual: 1,\n  which isn't equal to 2","actual_expr":"1","actual":"1","matcher_description":"is equal to 2","match_explanation":"which isn't equal to 2","custom_message":"Custom","traces":[]}]"#
+            )
+        )
+    }
+
+    #[test]
+    fn render_json_includes_scoped_traces() -> Result<()> {
+        let failure = {
+            scoped_trace!("Trace {}", 1);
+            TestAssertionFailure::create("Failure".into())
+        };
+
+        let json = render_json(&[record(
+            "a::failing",
+            TestStatus::Failed,
+            vec![FailureRecord::from_assertion_failure(&failure)],
+        )]);
+
+        verify_that!(
+            json,
+            contains_regex(
+                r#""traces":\[\{"file":"[^"]*test_report.rs","line":[0-9]+,"message":"Trace 1"\}\]"#
+            )
+        )
+    }
+
+    #[test]
+    fn render_json_leaves_out_missing_assertion_fields() -> Result<()> {
+        let failure = FailureRecord {
+            message: "panicked: \"oops\"\u{1B}".into(),
+            file: "src/lib.rs".into(),
+            line: 12,
+            assertion: None,
+        };
+
+        let json = render_json(&[record("a::failing", TestStatus::Failed, vec![failure])]);
+
+        verify_that!(
+            json,
+            contains_substring(
+                r#"{"file":"src/lib.rs","line":12,"message":"panicked: \"oops\"\u001b","description":null,"actual_expr":null,"actual":null,"matcher_description":null,"match_explanation":null,"custom_message":null,"traces":[]}"#
+            )
+        )
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for retrying failed tests.
+//!
+//! A test is retried up to the number of times given by
+//! `#[gtest(retries = N)]` or, if that is absent, by the environment variable
+//! GTEST_RETRIES. A test which fails and then passes on a later attempt is
+//! flaky. This is announced on the standard error, bypassing the output
+//! capture of the test harness, together with the failures of the earlier
+//! attempts.
+
+use crate::__internal_macro_support::catch_unwind_async;
+use crate::internal::test_outcome::TestFailure;
+use crate::internal::test_report;
+use std::future::Future;
+use std::io::Write as _;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+
+/// Environment variable specifying how many times a failed test is retried.
+const GTEST_RETRIES: &str = "GTEST_RETRIES";
+
+/// Runs the test `name` by calling `attempt` until it passes or `retries`
+/// retries have been made.
+///
+/// If `retries` is `None`, the number of retries is taken from GTEST_RETRIES.
+/// Each attempt must initialize and close its own
+/// [`TestOutcome`](crate::internal::test_outcome::TestOutcome), which a sync test
+/// with a timeout does on the thread running its body.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub fn run_with_retries(
+    name: &'static str,
+    retries: Option<u32>,
+    mut attempt: impl FnMut() -> Result<(), TestFailure>,
+) -> Result<(), TestFailure> {
+    let mut attempts = Attempts::new(name, retries);
+    loop {
+        if attempts.is_last() {
+            return attempts.finish(attempt());
+        }
+        match catch_unwind(AssertUnwindSafe(&mut attempt)) {
+            Ok(Ok(())) => return attempts.finish(Ok(())),
+            Ok(Err(_)) | Err(_) => attempts.retry(),
+        }
+    }
+}
+
+/// Like [`run_with_retries`], for `async` tests.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub async fn run_with_retries_async<F: Future<Output = Result<(), TestFailure>>>(
+    name: &'static str,
+    retries: Option<u32>,
+    mut attempt: impl FnMut() -> F,
+) -> Result<(), TestFailure> {
+    let mut attempts = Attempts::new(name, retries);
+    loop {
+        if attempts.is_last() {
+            return attempts.finish(attempt().await);
+        }
+        match catch_unwind_async(attempt()).await {
+            Ok(Ok(())) => return attempts.finish(Ok(())),
+            Ok(Err(_)) | Err(_) => attempts.retry(),
+        }
+    }
+}
+
+/// The attempts made so far to run a test.
+struct Attempts {
+    name: &'static str,
+    /// The number of the current attempt, starting at 1.
+    current: u32,
+    total: u32,
+    /// The failure messages of each failed attempt.
+    failures: Vec<Vec<String>>,
+}
+
+impl Attempts {
+    fn new(name: &'static str, retries: Option<u32>) -> Self {
+        let retries = retries.unwrap_or_else(retries_from_environment);
+        Self { name, current: 1, total: retries.saturating_add(1), failures: vec![] }
+    }
+
+    fn is_last(&self) -> bool {
+        self.current >= self.total
+    }
+
+    /// Records that the current attempt failed and starts the next one.
+    fn retry(&mut self) {
+        println!("Attempt {} of {} failed. Retrying.", self.current, self.total);
+        self.failures.push(test_report::take_failures());
+        self.current += 1;
+    }
+
+    /// Reports the outcome of the test after its final attempt `result`.
+    fn finish(&self, result: Result<(), TestFailure>) -> Result<(), TestFailure> {
+        match result {
+            Ok(()) if !self.failures.is_empty() => {
+                let _ = std::io::stderr().write_all(self.flaky_report().as_bytes());
+            }
+            Err(_) if self.total > 1 => println!("Failed in all {} attempts.", self.total),
+            _ => {}
+        }
+        result
+    }
+
+    fn flaky_report(&self) -> String {
+        let mut report =
+            format!("FLAKY {}: passed on attempt {} of {}\n", self.name, self.current, self.total);
+        for (index, failures) in self.failures.iter().enumerate() {
+            report.push_str(&format!("  Attempt {} failed:\n", index + 1));
+            for line in failures.iter().flat_map(|failure| failure.lines()) {
+                report.push_str(&format!("    {line}\n"));
+            }
+        }
+        report
+    }
+}
+
+fn retries_from_environment() -> u32 {
+    static RETRIES: OnceLock<u32> = OnceLock::new();
+    *RETRIES.get_or_init(|| {
+        let Ok(value) = std::env::var(GTEST_RETRIES) else {
+            return 0;
+        };
+        value.parse().unwrap_or_else(|_| {
+            eprintln!("invalid value \"{value}\" of ${GTEST_RETRIES}; tests are not retried");
+            0
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn flaky_report_lists_failures_of_earlier_attempts() -> Result<()> {
+        let attempts = Attempts {
+            name: "my_test",
+            current: 3,
+            total: 4,
+            failures: vec![vec!["Value of: x\n  at a.rs:1:1".into()], vec![]],
+        };
+
+        verify_that!(
+            attempts.flaky_report(),
+            eq(indoc::indoc! {"
+                FLAKY my_test: passed on attempt 3 of 4
+                  Attempt 1 failed:
+                    Value of: x
+                      at a.rs:1:1
+                  Attempt 2 failed:
+            "})
+        )
+    }
+}
@@ -5,6 +5,13 @@
 /// * GTEST_SHARD_INDEX: number of this shard
 /// * GTEST_SHARD_STATUS_FILE: touch this file to indicate support for sharding.
 ///
+/// A test runs on the shard `test_shard_hash(name) % GTEST_TOTAL_SHARDS`,
+/// where `name` is the fully qualified name of the test, e.g.
+/// `my_crate::tests::my_test`. The hash is the 64-bit FNV-1a hash of the name
+/// encoded as UTF-8. It is stable across toolchains and versions of this
+/// library, so that external tools can compute the assignment of tests to
+/// shards.
+///
 /// See also <https://google.github.io/googletest/advanced.html>
 use std::cell::OnceCell;
 use std::env::{var, var_os};
@@ -38,12 +45,31 @@ impl Default for Sharding {
     }
 }
 
-pub fn test_should_run(test_case_hash: u64) -> bool {
+pub fn test_should_run(test_name: &str) -> bool {
+    let test_case_hash = test_shard_hash(test_name);
     SHARDING.with(|sharding_cell| {
         sharding_cell.get_or_init(Sharding::from_environment).test_should_run(test_case_hash)
     })
 }
 
+/// Returns the index of the shard on which the test `test_name` runs.
+pub fn test_shard_index(test_name: &str) -> u64 {
+    let test_case_hash = test_shard_hash(test_name);
+    SHARDING.with(|sharding_cell| {
+        test_case_hash % sharding_cell.get_or_init(Sharding::from_environment).total_shards
+    })
+}
+
+/// Computes the hash which assigns the test with the fully qualified name
+/// `test_name` to a shard: the 64-bit FNV-1a hash of its UTF-8 encoding.
+pub fn test_shard_hash(test_name: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    test_name
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
 fn get_var(keys: &[&str]) -> Option<String> {
     for key in keys {
         if let Ok(value) = var(OsStr::new(key)) {
@@ -105,3 +131,25 @@ fn create_status_file(path: &Path) -> std::io::Result<()> {
 
     File::create(path).map(|_| ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_shard_hash_is_fnv1a() -> Result<()> {
+        // Reference values of the 64-bit FNV-1a hash.
+        verify_that!(test_shard_hash(""), eq(0xcbf29ce484222325))?;
+        verify_that!(test_shard_hash("a"), eq(0xaf63dc4c8601ec8c))?;
+        verify_that!(test_shard_hash("foobar"), eq(0x85944171f73967e8))
+    }
+
+    #[test]
+    fn test_shard_hash_depends_on_module_path() -> Result<()> {
+        verify_that!(
+            test_shard_hash("my_crate::tests::my_test"),
+            not(eq(test_shard_hash("my_crate::other_tests::my_test")))
+        )
+    }
+}
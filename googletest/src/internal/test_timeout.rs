@@ -0,0 +1,170 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for `#[gtest(timeout = "...")]`.
+//!
+//! A synchronous test cannot be interrupted, so its body runs on a separate
+//! thread while the thread of the test harness waits for it. That thread
+//! records its failures in the report entry of the test. If the body does not
+//! complete in time, the test fails and the thread running it is abandoned and
+//! detached from the report entry, so that its later failures do not count
+//! against a retry of the test. The fixtures of the test are set up on that
+//! thread as well and are only torn down if it completes. An `async` test is
+//! instead wrapped in a future which stops polling the body once the timeout
+//! has elapsed.
+
+use crate::internal::scoped_trace::TraceStack;
+use crate::internal::test_outcome::{TestAssertionFailure, TestFailure, TestOutcome};
+use crate::internal::test_report::{self, RunningTestHandle};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+enum WorkerMessage {
+    Started(TraceStack),
+    Finished(Result<(), TestFailure>),
+}
+
+/// Runs the body `test` of a synchronous test on a separate thread and fails the
+/// test if it does not complete within `timeout`.
+///
+/// The closure `test` sets up and closes the test context on that thread.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[track_caller]
+pub fn run_with_timeout(
+    timeout: Duration,
+    test: impl FnOnce() -> Result<(), TestFailure> + Send + 'static,
+) -> Result<(), TestFailure> {
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+    let running_test = RunningTestHandle::current();
+    let mut builder = thread::Builder::new();
+    // Reuse the name given by the test harness so that panic messages refer to
+    // the test.
+    if let Some(name) = thread::current().name() {
+        builder = builder.name(name.into());
+    }
+    let worker = builder
+        .spawn(move || {
+            running_test.attach();
+            let _ = sender.send(WorkerMessage::Started(TraceStack::current()));
+            let result = test();
+            let _ = sender.send(WorkerMessage::Finished(result));
+        })
+        .expect("failed to spawn the thread running the test");
+
+    let mut trace_stack = None;
+    loop {
+        match receiver.recv_timeout(timeout.saturating_sub(start.elapsed())) {
+            Ok(WorkerMessage::Started(stack)) => trace_stack = Some(stack),
+            Ok(WorkerMessage::Finished(result)) => return result,
+            Err(RecvTimeoutError::Disconnected) => {
+                // The worker only hangs up without a result if the test panicked.
+                let payload = worker.join().expect_err("the test thread exited without a result");
+                std::panic::resume_unwind(payload);
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+        }
+    }
+
+    test_report::detach_other_threads();
+    let mut failure = timeout_failure(timeout);
+    failure.traces = trace_stack.map(|stack| stack.traces()).unwrap_or_default();
+    // The test outcome of the abandoned thread is never closed, so the test
+    // ends with an outcome of its own on this thread.
+    TestOutcome::init_current_test_outcome();
+    TestOutcome::close_current_test_outcome(Err(failure))
+}
+
+/// Wraps the body `future` of an `async` test so that the test fails if it
+/// does not complete within `timeout`.
+///
+/// The returned future resolves to `None` if the timeout elapsed. In that case,
+/// the failure has already been recorded.
+///
+/// This is intended only for use by the attribute macro `#[gtest]`.
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+#[track_caller]
+pub fn with_timeout<F: Future>(timeout: Duration, future: F) -> Timeout<F> {
+    Timeout {
+        future: Box::pin(future),
+        timeout,
+        deadline: Instant::now() + timeout,
+        waker: None,
+        location: std::panic::Location::caller(),
+    }
+}
+
+/// The future returned by [`with_timeout`].
+///
+/// **For internal use only. API stablility is not guaranteed!**
+#[doc(hidden)]
+pub struct Timeout<F> {
+    future: Pin<Box<F>>,
+    timeout: Duration,
+    deadline: Instant,
+    /// The waker to be woken at the deadline, shared with the timer thread
+    /// once it has been started.
+    waker: Option<Arc<Mutex<Waker>>>,
+    location: &'static std::panic::Location<'static>,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            timeout_failure(self.timeout)
+                .with_fake_location(
+                    self.location.file(),
+                    self.location.line(),
+                    self.location.column(),
+                )
+                .log();
+            return Poll::Ready(None);
+        }
+        match &self.waker {
+            Some(waker) => waker.lock().unwrap_or_else(|e| e.into_inner()).clone_from(cx.waker()),
+            None => {
+                let waker = Arc::new(Mutex::new(cx.waker().clone()));
+                let timer_waker = Arc::clone(&waker);
+                thread::spawn(move || {
+                    thread::sleep(remaining);
+                    timer_waker.lock().unwrap_or_else(|e| e.into_inner()).wake_by_ref();
+                });
+                self.waker = Some(waker);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+#[track_caller]
+fn timeout_failure(timeout: Duration) -> TestAssertionFailure {
+    TestAssertionFailure::create(format!("Test did not complete within {timeout:?}"))
+}
@@ -22,15 +22,21 @@ extern crate quickcheck;
 #[macro_use]
 pub mod assertions;
 pub mod description;
+pub mod environment;
 pub mod fixtures;
 #[macro_use]
 pub mod fmt;
 pub mod internal;
+pub mod listener;
 pub mod matcher;
 pub mod matcher_support;
 pub mod matchers;
+mod test_info;
+pub mod thread;
 
 pub use googletest_macro::__abbreviated_stringify;
+pub use internal::scoped_trace::with_trace;
+pub use test_info::TestInfo;
 
 /// Re-exports of the symbols in this crate which are most likely to be used.
 ///
@@ -47,22 +53,31 @@ pub use googletest_macro::__abbreviated_stringify;
 /// }
 /// ```
 pub mod prelude {
-    pub use super::fixtures::{ConsumableFixture, Fixture, FixtureOf, StaticFixture};
+    pub use super::environment::Environment;
+    pub use super::fixtures::{
+        AsyncConsumableFixture, AsyncFixture, ConsumableFixture, DependentFixture, Fixture,
+        FixtureOf, ModuleScoped, StaticFixture,
+    };
     pub use super::gtest;
+    pub use super::gtest_environment;
     pub use super::matcher::{Matcher, MatcherBase};
     pub use super::matchers::*;
+    pub use super::record_property;
     pub use super::scoped_trace;
     pub use super::verify_current_test_outcome;
+    pub use super::with_trace;
     pub use super::GoogleTestSupport;
     pub use super::OrFail;
     pub use super::Result;
     // Assert macros
     pub use super::{
         add_failure, add_failure_at, assert_false, assert_ok, assert_pred, assert_that,
-        assert_true, expect_eq, expect_false, expect_float_eq, expect_ge, expect_gt, expect_le,
-        expect_lt, expect_ne, expect_near, expect_ok, expect_pred, expect_that, expect_true, fail,
-        succeed, verify_eq, verify_false, verify_float_eq, verify_ge, verify_gt, verify_le,
-        verify_lt, verify_ne, verify_near, verify_ok, verify_pred, verify_that, verify_true,
+        assert_true, expect_death, expect_eq, expect_exit, expect_false, expect_float_eq,
+        expect_ge, expect_gt, expect_le, expect_lt, expect_ne, expect_near, expect_ok,
+        expect_panics, expect_pred, expect_that, expect_true, fail, gtest_skip, skip, succeed,
+        verify_death, verify_eq, verify_exit, verify_false, verify_float_eq, verify_ge, verify_gt,
+        verify_le, verify_lt, verify_ne, verify_near, verify_ok, verify_panics, verify_pred,
+        verify_that, verify_true,
     };
 }
 
@@ -78,7 +93,121 @@ macro_rules! scoped_trace {
     };
 }
 
+/// Records a key/value property of the running test, like `RecordProperty` in
+/// GoogleTest C++.
+///
+/// The value may be of any type implementing [`Display`][std::fmt::Display].
+/// The property is printed in the output of the test and attached to the test
+/// in the XML or JSON report requested with GTEST_OUTPUT. Recording a property
+/// again replaces its value.
+///
+/// ```ignore
+/// #[gtest]
+/// fn handles_random_input() {
+///     let seed = rand::random::<u64>();
+///     record_property!("seed", seed);
+///     ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! record_property {
+    ($key:expr, $value:expr $(,)?) => {
+        $crate::internal::test_report::record_property($key, $value)
+    };
+}
+
+/// Defines the `main` function of a test target built with `harness = false`,
+/// running the tests annotated with `#[gtest]` with a runner offering the
+/// command line of GoogleTest C++.
+///
+/// ```toml
+/// [[test]]
+/// name = "my_tests"
+/// harness = false
+/// ```
+///
+/// ```ignore
+/// use googletest::prelude::*;
+///
+/// googletest::main!();
+///
+/// #[gtest]
+/// fn two_plus_two_is_four() {
+///     expect_that!(2 + 2, eq(4));
+/// }
+/// ```
+///
+/// Unlike the Rust test harness, the runner can repeat and shuffle the tests:
+///
+/// ```sh
+/// cargo test --test my_tests -- --gtest_filter='*four*' --gtest_repeat=10 --gtest_shuffle
+/// ```
+///
+/// The tests run one after the other on the main thread, and their output is
+/// not captured. Run the test binary with `--help` for the supported flags.
+/// The static fixtures and the global test environments are torn down after
+/// the last test.
+///
+/// The macro requires the feature `main` of this crate, with which the tests
+/// register themselves before `main`:
+///
+/// ```toml
+/// [dev-dependencies]
+/// googletest = { version = "...", features = ["main"] }
+/// ```
+///
+/// This works on the same platforms as
+/// [global test environments][crate::environment], and the macro does not
+/// compile on other platforms. Tests which have another test attribute, such
+/// as `#[tokio::test]`, or use rstest are only known to the Rust test harness.
+/// They are not run, and the runner prints a warning listing them.
+#[macro_export]
+macro_rules! main {
+    () => {
+        $crate::__googletest__require_main_feature!();
+
+        // The platforms of `constructor_section_targets` in `googletest_macro`.
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_vendor = "apple",
+            windows,
+        )))]
+        compile_error!(
+            "googletest::main!() is not supported on this target, where tests cannot register \
+            themselves before `main`."
+        );
+
+        fn main() -> ::std::process::ExitCode {
+            $crate::internal::test_main::run_tests()
+        }
+    };
+}
+
+#[cfg(feature = "main")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __googletest__require_main_feature {
+    () => {};
+}
+
+#[cfg(not(feature = "main"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __googletest__require_main_feature {
+    () => {
+        compile_error!(
+            "googletest::main!() requires the feature `main` of googletest, with which the tests \
+            register themselves."
+        );
+    };
+}
+
 pub use googletest_macro::gtest;
+pub use googletest_macro::gtest_environment;
 pub use googletest_macro::test;
 
 use internal::test_outcome::{TestAssertionFailure, TestOutcome};
@@ -340,42 +469,100 @@ impl<T> OrFail for Option<T> {
 
 #[doc(hidden)]
 pub mod __internal_macro_support {
-    use crate::fixtures::Fixture;
+    use crate::fixtures::{AsyncConsumableFixture, AsyncFixture, ConsumableFixture};
+    use std::future::Future;
+    use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    pub use crate::fixtures::FixtureScope;
 
-    pub struct FixtureTearDownOnDrop<T: Fixture> {
+    /// Sets up the consumable fixture `F` of a synchronous test.
+    pub fn set_up_consumable_fixture<F: ConsumableFixture>() -> crate::Result<F> {
+        let fixture = F::set_up()?;
+        crate::fixtures::notify_set_up::<F>();
+        Ok(fixture)
+    }
+
+    /// Sets up the consumable fixture `F` of an `async` test.
+    pub async fn set_up_async_consumable_fixture<F: AsyncConsumableFixture>() -> crate::Result<F> {
+        let fixture = F::set_up().await?;
+        crate::fixtures::notify_set_up::<F>();
+        Ok(fixture)
+    }
+
+    pub struct AsyncFixtureTearDownOnDrop<T: AsyncFixture> {
         fixture: Option<T>,
     }
 
-    impl<T: Fixture> FixtureTearDownOnDrop<T> {
+    impl<T: AsyncFixture> AsyncFixtureTearDownOnDrop<T> {
         pub fn new(fixture: T) -> Self {
+            crate::fixtures::notify_set_up::<T>();
             Self { fixture: Some(fixture) }
         }
-        pub fn tear_down(mut self) -> crate::Result<()> {
+        pub async fn tear_down(mut self) -> crate::Result<()> {
             if let Some(fixture) = self.fixture.take() {
-                fixture.tear_down()
+                let result = fixture.tear_down().await;
+                crate::fixtures::notify_tear_down::<T>();
+                result
             } else {
                 Ok(())
             }
         }
     }
 
-    impl<T: Fixture> AsRef<T> for FixtureTearDownOnDrop<T> {
+    impl<T: AsyncFixture> AsRef<T> for AsyncFixtureTearDownOnDrop<T> {
         fn as_ref(&self) -> &T {
             self.fixture.as_ref().unwrap()
         }
     }
 
-    impl<T: Fixture> AsMut<T> for FixtureTearDownOnDrop<T> {
+    impl<T: AsyncFixture> AsMut<T> for AsyncFixtureTearDownOnDrop<T> {
         fn as_mut(&mut self) -> &mut T {
             self.fixture.as_mut().unwrap()
         }
     }
 
-    impl<T: Fixture> Drop for FixtureTearDownOnDrop<T> {
+    impl<T: AsyncFixture> Drop for AsyncFixtureTearDownOnDrop<T> {
+        // The generated test awaits `tear_down`, also after a timeout, so the
+        // fixture is only left here if the whole test future is dropped before
+        // completing. Its teardown cannot be awaited in `drop` without blocking
+        // the executor, so the fixture is dropped as is.
         fn drop(&mut self) {
-            if let Some(fixture) = self.fixture.take() {
-                fixture.tear_down().unwrap();
+            if self.fixture.take().is_some() {
+                eprintln!(
+                    "The fixture {} was not torn down as its test did not complete.",
+                    std::any::type_name::<T>()
+                );
+            }
+        }
+    }
+
+    /// Runs `future`, returning a panic while polling it as an `Err` instead
+    /// of unwinding, like [`std::panic::catch_unwind`].
+    pub fn catch_unwind_async<F: Future>(future: F) -> CatchUnwind<F> {
+        CatchUnwind { future: Box::pin(future) }
+    }
+
+    pub struct CatchUnwind<F> {
+        future: Pin<Box<F>>,
+    }
+
+    impl<F: Future> Future for CatchUnwind<F> {
+        type Output = std::thread::Result<F::Output>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match catch_unwind(AssertUnwindSafe(|| self.future.as_mut().poll(cx))) {
+                Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+                Ok(Poll::Pending) => Poll::Pending,
+                Err(payload) => Poll::Ready(Err(payload)),
             }
         }
     }
+
+    /// Returns the output of a future run by [`catch_unwind_async`], resuming
+    /// the panic it caught if any.
+    pub fn resume_unwind_on_panic<T>(result: std::thread::Result<T>) -> T {
+        result.unwrap_or_else(|payload| resume_unwind(payload))
+    }
 }
@@ -0,0 +1,188 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications of the events of running tests, for custom reporters and IDE
+//! integrations.
+//!
+//! A [`TestEventListener`] registered with [`add_test_event_listener`] is
+//! notified when `#[gtest]` tests start and end, when assertions fail, when
+//! scoped traces are pushed and when fixtures are set up and torn down. Any
+//! number of listeners may be registered; each event is delivered to all of
+//! them in the order of their registration.
+//!
+//! ```ignore
+//! struct FailureCounter(AtomicUsize);
+//!
+//! impl TestEventListener for FailureCounter {
+//!     fn on_assertion_failure(&self, _: Option<&TestInfo>, _: &TestAssertionFailure) {
+//!         self.0.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! googletest::listener::add_test_event_listener(FailureCounter(AtomicUsize::new(0)));
+//! ```
+//!
+//! Tests run concurrently, so events of different tests interleave. Events are
+//! delivered on the thread where they occur, which for events of a test is a
+//! thread running the test. Listeners must not make assertions themselves.
+
+pub use crate::internal::scoped_trace::TraceInfo;
+use crate::internal::test_outcome::TestAssertionFailure;
+use crate::TestInfo;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+/// The listeners registered so far, in the order of their registration.
+static LISTENERS: RwLock<Vec<Arc<dyn TestEventListener>>> = RwLock::new(Vec::new());
+
+/// Receives the events of running tests.
+///
+/// All methods do nothing by default, so that a listener implements only
+/// those for the events it is interested in.
+pub trait TestEventListener: Send + Sync {
+    /// Called when the test `test` starts, before its fixtures are set up.
+    fn on_test_start(&self, _test: &TestInfo) {}
+
+    /// Called when an assertion fails, be it fatal or not.
+    ///
+    /// `test` is the test running on the current thread, if any.
+    fn on_assertion_failure(&self, _test: Option<&TestInfo>, _failure: &TestAssertionFailure) {}
+
+    /// Called when a scoped trace is pushed with
+    /// [`scoped_trace!`][crate::scoped_trace] or
+    /// [`with_trace`][crate::with_trace].
+    fn on_trace_pushed(&self, _trace: &TraceInfo) {}
+
+    /// Called when the test `test` ends with `outcome`, after its fixtures have
+    /// been torn down, having run for `duration`.
+    fn on_test_end(&self, _test: &TestInfo, _outcome: Outcome, _duration: Duration) {}
+
+    /// Called when the fixture of type `fixture` has been set up successfully.
+    ///
+    /// A [`StaticFixture`][crate::fixtures::StaticFixture] is set up only
+    /// once, while the first test using it runs.
+    fn on_fixture_set_up(&self, _fixture: &'static str) {}
+
+    /// Called when the fixture of type `fixture` has been torn down, whether
+    /// successfully or not.
+    ///
+    /// A [`StaticFixture`][crate::fixtures::StaticFixture] is torn down after
+    /// the last test has ended.
+    fn on_fixture_tear_down(&self, _fixture: &'static str) {}
+}
+
+/// How a test ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The test passed. A `#[should_panic]` test passes when it panics.
+    Passed,
+    /// The test failed.
+    Failed,
+    /// The test was skipped with [`skip!`][crate::skip].
+    Skipped,
+    /// The test failed as expected by `#[gtest(expect_failure)]`.
+    ExpectedFailure,
+}
+
+/// Registers `listener` to be notified of all subsequent events.
+pub fn add_test_event_listener(listener: impl TestEventListener + 'static) {
+    LISTENERS.write().unwrap_or_else(PoisonError::into_inner).push(Arc::new(listener));
+}
+
+/// Delivers an event to all registered listeners with `notify`.
+///
+/// The listeners are called without holding the lock, so that they may
+/// register other listeners.
+pub(crate) fn notify(notify: impl Fn(&dyn TestEventListener)) {
+    let listeners = LISTENERS.read().unwrap_or_else(PoisonError::into_inner).clone();
+    for listener in &listeners {
+        notify(listener.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as googletest;
+    use crate::prelude::*;
+    use std::sync::Mutex;
+
+    /// Records the events of the test `test_name`.
+    struct EventRecorder {
+        test_name: &'static str,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl EventRecorder {
+        fn record(&self, event: String) {
+            if TestInfo::current().is_some_and(|test| test.name() == self.test_name) {
+                self.events.lock().unwrap().push(event);
+            }
+        }
+    }
+
+    impl TestEventListener for EventRecorder {
+        fn on_trace_pushed(&self, trace: &TraceInfo) {
+            self.record(format!("trace: {}", trace.message));
+        }
+
+        fn on_fixture_set_up(&self, fixture: &'static str) {
+            self.record(format!("set up {}", fixture.rsplit("::").next().unwrap()));
+        }
+
+        fn on_fixture_tear_down(&self, fixture: &'static str) {
+            self.record(format!("tear down {}", fixture.rsplit("::").next().unwrap()));
+        }
+    }
+
+    struct ListenedFixture;
+
+    impl Fixture for ListenedFixture {
+        fn set_up() -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[gtest]
+    fn all_listeners_receive_events() -> Result<()> {
+        let first = Arc::new(Mutex::new(vec![]));
+        let second = Arc::new(Mutex::new(vec![]));
+        let test_name = "all_listeners_receive_events";
+        add_test_event_listener(EventRecorder { test_name, events: first.clone() });
+        add_test_event_listener(EventRecorder { test_name, events: second.clone() });
+
+        {
+            scoped_trace!("checking listeners");
+            let mut fixtures = crate::fixtures::FixtureScope::new();
+            fixtures.set_up_argument::<ListenedFixture>()?;
+            fixtures.tear_down()?;
+        }
+
+        let first = first.lock().unwrap().clone();
+        verify_that!(
+            first,
+            elements_are![
+                eq("trace: checking listeners"),
+                eq("set up ListenedFixture"),
+                eq("tear down ListenedFixture"),
+            ]
+        )?;
+        let second = second.lock().unwrap().clone();
+        verify_that!(second, eq(&first))
+    }
+}
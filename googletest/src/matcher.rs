@@ -15,7 +15,7 @@
 //! The components required to implement matchers.
 
 use crate::description::Description;
-use crate::internal::test_outcome::TestAssertionFailure;
+use crate::internal::test_outcome::{MatchFailure, TestAssertionFailure};
 use crate::matchers::__internal_unstable_do_not_depend_on_these::ConjunctionMatcher;
 use crate::matchers::__internal_unstable_do_not_depend_on_these::DisjunctionMatcher;
 pub use googletest_macro::MatcherBase;
@@ -245,15 +245,23 @@ pub(crate) fn create_assertion_failure<T: Debug + Copy>(
     } else {
         actual_formatted
     };
+    let matcher_description = matcher.describe(MatcherResult::Match);
+    let explanation = matcher.explain_match(actual);
+    let match_explanation = explanation.to_string();
     TestAssertionFailure::create(format!(
         "\
 Value of: {actual_expr}
-Expected: {}
+Expected: {matcher_description}
 Actual: {actual_formatted},
 {}",
-        matcher.describe(MatcherResult::Match),
-        matcher.explain_match(actual).indent(),
+        explanation.indent(),
     ))
+    .with_match_failure(MatchFailure {
+        actual_expr,
+        actual: actual_formatted,
+        matcher_description: matcher_description.to_string(),
+        match_explanation,
+    })
 }
 
 /// The result of applying a [`Matcher`] on an actual value.
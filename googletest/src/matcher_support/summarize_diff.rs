@@ -246,7 +246,9 @@ impl Display for BufferedSummary<'_> {
     }
 }
 
+#[derive(Default)]
 enum Buffer<'a> {
+    #[default]
     Empty,
     CommonLines(Vec<&'a str>),
     ExtraActualLineChunk(&'a str),
@@ -307,18 +309,12 @@ impl<'a> Buffer<'a> {
     }
 }
 
-impl Default for Buffer<'_> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 thread_local! {
   pub(crate) static USE_COLOR: Cell<bool> = Cell::new(stdout_supports_color());
 }
 
 #[rustversion::since(1.70)]
-fn stdout_supports_color() -> bool {
+pub(crate) fn stdout_supports_color() -> bool {
     #[allow(clippy::incompatible_msrv)]
     match (is_env_var_set("NO_COLOR"), is_env_var_set("FORCE_COLOR")) {
         (true, _) => false,
@@ -328,7 +324,7 @@ fn stdout_supports_color() -> bool {
 }
 
 #[rustversion::not(since(1.70))]
-fn stdout_supports_color() -> bool {
+pub(crate) fn stdout_supports_color() -> bool {
     is_env_var_set("FORCE_COLOR") && !is_env_var_set("NO_COLOR")
 }
 
@@ -0,0 +1,93 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::internal::test_sharding::test_shard_index;
+
+/// Information about the running test, such as its name and location.
+///
+/// This lets fixtures and helpers derive per-test names, for instance of
+/// directories or golden files, without hard-coding them:
+///
+/// ```ignore
+/// #[gtest]
+/// fn renders_page() -> Result<()> {
+///     let info = TestInfo::current().or_fail()?;
+///     let golden = format!("testdata/{}.golden", info.name());
+///     ...
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestInfo {
+    pub(crate) full_name: &'static str,
+    pub(crate) module_path: &'static str,
+    pub(crate) file: &'static str,
+    pub(crate) line: u32,
+    pub(crate) should_panic: bool,
+}
+
+impl TestInfo {
+    /// Returns the information about the test running on the current thread,
+    /// or `None` if the current thread is not running a test annotated with
+    /// `#[gtest]`.
+    ///
+    /// Threads on which `#[gtest]` runs a test on behalf of the test thread,
+    /// for instance to enforce its timeout, also see the test.
+    pub fn current() -> Option<Self> {
+        crate::internal::test_report::running_test_info()
+    }
+
+    /// The fully qualified name of the test, as used by test filters, e.g.
+    /// `my_crate::tests::is_even::case_1`.
+    pub fn full_name(&self) -> &'static str {
+        self.full_name
+    }
+
+    /// The path of the module in which the test function is defined, e.g.
+    /// `my_crate::tests`.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The name of the test relative to its module, e.g. `is_even::case_1`
+    /// for a case of the parameterized test `is_even`, or the name of the test
+    /// function otherwise.
+    pub fn name(&self) -> &'static str {
+        self.full_name
+            .strip_prefix(self.module_path)
+            .and_then(|name| name.strip_prefix("::"))
+            .unwrap_or(self.full_name)
+    }
+
+    /// The source file in which the test is defined.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of [`TestInfo::file`] at which the test is defined, that is,
+    /// the line of its `#[gtest]` attribute.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The index of the shard on which the test runs, given the total number
+    /// of shards in GTEST_TOTAL_SHARDS. This is 0 if tests are not sharded.
+    pub fn shard_index(&self) -> u64 {
+        test_shard_index(self.full_name)
+    }
+
+    /// Whether the test is annotated with `#[should_panic]`.
+    pub fn should_panic(&self) -> bool {
+        self.should_panic
+    }
+}
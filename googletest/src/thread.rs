@@ -0,0 +1,196 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for assertions in threads spawned by a test.
+//!
+//! Non-fatal assertions such as [`expect_that!`][crate::expect_that] record
+//! their failures in the test running on the current thread. A thread spawned
+//! by the test does not run the test, so assertions there panic. The functions
+//! of this module spawn threads in the [`TestContext`] of the test instead, so
+//! that their failures are recorded against the test, and they inherit the
+//! scoped traces active where they are spawned. A thread reaching the limit of
+//! GTEST_MAX_FAILURES_PER_TEST stops there, and the test stops at its next
+//! failure or once its body returns:
+//!
+//! ```ignore
+//! #[gtest]
+//! fn counter_is_thread_safe() {
+//!     let counter = Counter::default();
+//!     googletest::thread::scope(|s| {
+//!         for _ in 0..4 {
+//!             s.spawn(|| expect_that!(counter.increment(), gt(0)));
+//!         }
+//!     });
+//! }
+//! ```
+
+use crate::internal::scoped_trace::{get_scoped_traces, PushedTraces, TraceInfo};
+use crate::internal::test_outcome::TestOutcomeHandle;
+use crate::internal::test_report::RunningTestHandle;
+use std::thread::{JoinHandle, ScopedJoinHandle};
+
+/// A handle to the test running on the current thread, which lets other
+/// threads make assertions on its behalf.
+///
+/// ```ignore
+/// #[gtest]
+/// fn worker_reports_progress() {
+///     let context = TestContext::current().unwrap();
+///     let worker = std::thread::spawn(move || {
+///         context.run(|| expect_that!(do_work(), eq(42)));
+///     });
+///     worker.join().unwrap();
+/// }
+/// ```
+///
+/// Failures recorded after the test has completed cause a panic, as do
+/// assertions without a test context.
+#[derive(Clone)]
+pub struct TestContext {
+    outcome: TestOutcomeHandle,
+    running_test: RunningTestHandle,
+    traces: Vec<TraceInfo>,
+}
+
+impl TestContext {
+    /// Returns the context of the test running on the current thread, with the
+    /// scoped traces currently active, or `None` if the current thread is not
+    /// running a test annotated with `#[gtest]`.
+    pub fn current() -> Option<Self> {
+        let outcome = TestOutcomeHandle::current();
+        outcome.is_running().then(|| Self {
+            outcome,
+            running_test: RunningTestHandle::current(),
+            traces: get_scoped_traces(),
+        })
+    }
+
+    /// Runs `action` on the current thread on behalf of the test, with the
+    /// scoped traces of the context.
+    pub fn run<T>(&self, action: impl FnOnce() -> T) -> T {
+        let _attached = AttachedContext {
+            outcome: Some(self.outcome.clone().attach()),
+            running_test: Some(self.running_test.clone().attach()),
+        };
+        let _traces = PushedTraces::push(&self.traces);
+        action()
+    }
+}
+
+/// Restores the test context the current thread had before
+/// [`TestContext::run`].
+struct AttachedContext {
+    outcome: Option<TestOutcomeHandle>,
+    running_test: Option<RunningTestHandle>,
+}
+
+impl Drop for AttachedContext {
+    fn drop(&mut self) {
+        if let Some(outcome) = self.outcome.take() {
+            outcome.attach();
+        }
+        if let Some(running_test) = self.running_test.take() {
+            running_test.attach();
+        }
+    }
+}
+
+/// Runs `f` in `context`, if any.
+fn run_in_context<T>(context: Option<TestContext>, f: impl FnOnce() -> T) -> T {
+    match context {
+        Some(context) => context.run(f),
+        None => f(),
+    }
+}
+
+/// Spawns a thread running `f` in the context of the test running on the
+/// current thread, like [`std::thread::spawn`].
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let context = TestContext::current();
+    std::thread::spawn(move || run_in_context(context, f))
+}
+
+/// Creates a scope for spawning threads running in the context of the test
+/// running on the current thread, like [`std::thread::scope`].
+///
+/// All threads spawned in the scope are joined before this returns, so their
+/// failures are recorded before the test completes.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&Scope<'scope, 'env>) -> T,
+{
+    let context = TestContext::current();
+    std::thread::scope(|inner| f(&Scope { inner, context }))
+}
+
+/// A scope to spawn threads in, created by [`scope`].
+pub struct Scope<'scope, 'env: 'scope> {
+    inner: &'scope std::thread::Scope<'scope, 'env>,
+    context: Option<TestContext>,
+}
+
+impl<'scope> Scope<'scope, '_> {
+    /// Spawns a thread running `f` in the context of the test, like
+    /// [`std::thread::Scope::spawn`].
+    pub fn spawn<F, T>(&self, f: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let context = self.context.clone();
+        self.inner.spawn(move || run_in_context(context, f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::test_outcome::TestOutcome;
+    use crate::prelude::*;
+
+    #[test]
+    fn current_is_none_outside_test() -> Result<()> {
+        verify_that!(TestContext::current().is_none(), eq(true))
+    }
+
+    #[test]
+    fn failure_in_scoped_thread_fails_test() -> Result<()> {
+        TestOutcome::init_current_test_outcome();
+        scope(|s| {
+            s.spawn(|| expect_that!(1, eq(2)));
+        });
+        let result = TestOutcome::close_current_test_outcome::<&str>(Ok(()));
+
+        verify_that!(result.is_err(), eq(true))
+    }
+
+    #[test]
+    fn spawned_thread_inherits_scoped_traces() -> Result<()> {
+        TestOutcome::init_current_test_outcome();
+        let traces = {
+            crate::scoped_trace!("spawning thread");
+            spawn(get_scoped_traces).join().unwrap()
+        };
+        TestOutcome::close_current_test_outcome::<&str>(Ok(())).unwrap();
+
+        verify_that!(
+            traces.into_iter().map(|trace| trace.message).collect::<Vec<_>>(),
+            elements_are![eq("spawning thread")]
+        )
+    }
+}
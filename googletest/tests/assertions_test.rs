@@ -426,3 +426,56 @@ mod verify_pred {
         )
     }
 }
+
+mod verify_panics {
+    use googletest::prelude::*;
+    use googletest::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn passes_when_panic_message_matches() -> Result<()> {
+        verify_panics!(panic!("Intentional panic: {}", 42), contains_regex("panic: [0-9]+"))?;
+        verify_panics!(panic!("Intentional panic"), eq("Intentional panic"))
+    }
+
+    #[test]
+    fn fails_when_statement_does_not_panic() -> Result<()> {
+        let value = 1;
+        verify_that!(
+            verify_panics!(value + 1, contains_substring("overflow")),
+            err(displays_as(contains_substring(indoc! {r#"
+                Value of: value + 1
+                Expected: panics with a message which contains a substring "overflow"
+                Actual: does not panic
+                "#})))
+        )
+    }
+
+    #[test]
+    fn fails_when_panic_message_does_not_match() -> Result<()> {
+        verify_that!(
+            verify_panics!(panic!("Intentional panic"), eq("other panic")),
+            err(displays_as(contains_substring(indoc! {r#"
+                Value of: panic message of panic!("Intentional panic")
+                Expected: is equal to "other panic"
+                Actual: "Intentional panic",
+                "#})))
+        )
+    }
+
+    #[test]
+    fn fails_when_panic_payload_is_not_a_string() -> Result<()> {
+        verify_that!(
+            verify_panics!(std::panic::panic_any(42), anything()),
+            err(displays_as(contains_substring(
+                "panicked with a payload which is neither a &str nor a String"
+            )))
+        )
+    }
+
+    #[gtest]
+    fn expected_panics_do_not_fail_test() {
+        expect_panics!(panic!("Intentional panic"), anything());
+        expect_panics!(Vec::<u32>::new()[0], contains_substring("index out of bounds"));
+    }
+}
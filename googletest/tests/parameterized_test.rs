@@ -0,0 +1,75 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[gtest(params = [1, 2, 3])]
+    fn parameterized_test_receives_each_value(value: u32) -> Result<()> {
+        verify_that!(value, all!(ge(1), le(3)))
+    }
+
+    #[gtest(params = [2, 4])]
+    fn parameterized_test_without_return_type(value: u32) {
+        expect_that!(value % 2, eq(0));
+    }
+
+    #[gtest(params = [small = 1, large = 1_000_000])]
+    fn parameterized_test_with_labels(value: u32) -> Result<()> {
+        verify_that!(value, gt(0))
+    }
+
+    #[gtest(params = [(1, 2), (2, 4)])]
+    fn parameterized_test_with_tuple_parameter((input, expected): (u32, u32)) -> Result<()> {
+        verify_that!(input * 2, eq(expected))
+    }
+
+    #[gtest(params = ["a", "bc"])]
+    fn parameterized_test_with_string_parameter(value: &str) -> Result<()> {
+        verify_that!(value.len(), gt(0))
+    }
+
+    static SET_UP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingFixture(usize);
+
+    impl Fixture for CountingFixture {
+        fn set_up() -> Result<Self> {
+            Ok(Self(SET_UP_COUNT.fetch_add(1, Ordering::Relaxed) + 1))
+        }
+
+        fn tear_down(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[gtest(params = [10, 20])]
+    fn parameterized_test_with_fixture(value: usize, fixture: &CountingFixture) -> Result<()> {
+        verify_that!(value, ge(10))?;
+        verify_that!(fixture.0, gt(0))
+    }
+
+    #[gtest(params = [1])]
+    #[should_panic(expected = "See failure output above")]
+    fn parameterized_test_with_should_panic(value: u32) {
+        expect_that!(value, eq(2));
+    }
+
+    #[gtest]
+    fn parameterized_test_keeps_the_original_function() -> Result<()> {
+        parameterized_test_receives_each_value(2)
+    }
+}
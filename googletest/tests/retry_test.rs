@@ -0,0 +1,57 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[gtest(retries = 3)]
+    fn retried_test_passes_once_an_attempt_passes() -> Result<()> {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        expect_that!(attempt, ge(2));
+        verify_that!(attempt, ge(3))
+    }
+
+    #[gtest(retries = 1)]
+    fn retried_test_passes_after_panic() {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        if ATTEMPTS.fetch_add(1, Ordering::Relaxed) == 0 {
+            panic!("Intentional panic on the first attempt");
+        }
+    }
+
+    static SET_UPS: AtomicU32 = AtomicU32::new(0);
+
+    struct CountingFixture(u32);
+
+    impl ConsumableFixture for CountingFixture {
+        fn set_up() -> Result<Self> {
+            Ok(Self(SET_UPS.fetch_add(1, Ordering::Relaxed) + 1))
+        }
+    }
+
+    #[gtest(retries = 1)]
+    fn retried_test_sets_up_fixtures_again(fixture: CountingFixture) -> Result<()> {
+        verify_that!(fixture.0, eq(2))
+    }
+
+    #[gtest(retries = 1, timeout = "10s")]
+    fn retried_test_with_timeout() -> Result<()> {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        verify_that!(attempt, eq(2))
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn skipped_test_passes() {
+        skip!("Not supported here");
+        #[allow(unreachable_code)]
+        {
+            fail!("Unreachable").and_log_failure();
+        }
+    }
+
+    #[gtest]
+    fn skipped_test_returning_result_passes() -> Result<()> {
+        verify_that!(1, eq(1))?;
+        skip!("Skipped after {} assertion", 1);
+    }
+
+    #[gtest]
+    fn skipped_test_with_alias_passes() -> Result<()> {
+        gtest_skip!();
+    }
+
+    fn skip_in_helper() -> Result<()> {
+        skip!("Skipped in helper");
+    }
+
+    #[gtest]
+    fn skip_in_helper_ends_test_through_question_mark() -> Result<()> {
+        skip_in_helper()?;
+        fail!("Unreachable")
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use googletest::TestInfo;
+
+    #[gtest]
+    fn current_test_info_describes_running_test() -> Result<()> {
+        let info = TestInfo::current().or_fail()?;
+
+        expect_that!(
+            info.full_name(),
+            eq("test_info_test::tests::current_test_info_describes_running_test")
+        );
+        expect_that!(info.module_path(), eq("test_info_test::tests"));
+        expect_that!(info.name(), eq("current_test_info_describes_running_test"));
+        expect_that!(info.file(), eq(file!()));
+        expect_that!(info.line(), eq(line!() - 11));
+        expect_that!(info.shard_index(), eq(0));
+        verify_that!(info.should_panic(), eq(false))
+    }
+
+    #[gtest(params = [1])]
+    fn current_test_info_of_parameterized_test(_: u32) -> Result<()> {
+        let info = TestInfo::current().or_fail()?;
+
+        expect_that!(info.module_path(), eq("test_info_test::tests"));
+        verify_that!(info.name(), eq("current_test_info_of_parameterized_test::case_0"))
+    }
+
+    #[gtest]
+    #[should_panic]
+    fn current_test_info_of_should_panic_test() {
+        assert!(TestInfo::current().unwrap().should_panic());
+        panic!("Intentional panic");
+    }
+
+    #[gtest(timeout = "10s")]
+    fn current_test_info_on_timeout_thread() -> Result<()> {
+        verify_that!(
+            TestInfo::current().map(|info| info.name()),
+            some(eq("current_test_info_on_timeout_thread"))
+        )
+    }
+
+    #[test]
+    fn current_test_info_is_none_outside_gtest() -> Result<()> {
+        verify_that!(TestInfo::current(), none())
+    }
+}
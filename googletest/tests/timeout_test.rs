@@ -0,0 +1,54 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest(timeout = "10s")]
+    fn test_with_timeout_runs_assertions() -> Result<()> {
+        expect_that!(1, eq(1));
+        verify_that!(2, eq(2))
+    }
+
+    struct Counter(u32);
+
+    impl ConsumableFixture for Counter {
+        fn set_up() -> Result<Self> {
+            Ok(Self(1))
+        }
+    }
+
+    #[gtest(timeout = "1m30s")]
+    fn test_with_timeout_sets_up_fixtures(counter: Counter) -> Result<()> {
+        verify_that!(counter.0, eq(1))
+    }
+
+    #[gtest(timeout = "10s")]
+    #[should_panic]
+    fn test_with_timeout_reports_non_fatal_failure() {
+        expect_that!(1, eq(2));
+    }
+
+    #[gtest(timeout = "10s")]
+    #[should_panic(expected = "Intentional panic")]
+    fn test_with_timeout_propagates_panic() {
+        panic!("Intentional panic");
+    }
+
+    #[gtest(params = [1, 2], timeout = "10s")]
+    fn parameterized_test_with_timeout(value: u32) -> Result<()> {
+        verify_that!(value, gt(0))
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use num_traits::{Bounded, Zero};
+    use std::fmt::Debug;
+
+    #[gtest(types = [u8, i32, u64, f64])]
+    fn typed_test_runs_for_each_type<T: Zero + Default + Debug>() -> Result<()> {
+        verify_that!(T::default().is_zero(), is_true())
+    }
+
+    #[gtest(types = [u8, i16])]
+    fn typed_test_without_return_type<T: Bounded + PartialOrd + Debug>() {
+        expect_true!(T::min_value() < T::max_value());
+    }
+
+    #[gtest(types = [Vec<u8>, String, Option<i32>])]
+    fn typed_test_with_generic_types<T: Default + Debug + PartialEq>() -> Result<()> {
+        verify_that!(T::default(), eq(&T::default()))
+    }
+
+    struct DefaultFixture<T>(T);
+
+    impl<T: Default> ConsumableFixture for DefaultFixture<T> {
+        fn set_up() -> Result<Self> {
+            Ok(Self(T::default()))
+        }
+    }
+
+    #[gtest(types = [u32, i64])]
+    fn typed_test_with_fixture_depending_on_type<T: Zero + Debug>(
+        fixture: DefaultFixture<T>,
+    ) -> Result<()> {
+        verify_that!(fixture.0.is_zero(), is_true())
+    }
+
+    #[gtest(types = [u16])]
+    #[should_panic(expected = "See failure output above")]
+    fn typed_test_with_should_panic<T: Zero + Debug>() {
+        expect_false!(T::zero().is_zero());
+    }
+
+    trait Widen: Copy {
+        const MAX: Self;
+        type Wide: From<Self> + PartialOrd + Debug + Copy;
+    }
+
+    impl Widen for u8 {
+        const MAX: Self = u8::MAX;
+        type Wide = u16;
+    }
+
+    impl Widen for i16 {
+        const MAX: Self = i16::MAX;
+        type Wide = i32;
+    }
+
+    #[gtest(types = [u8, i16])]
+    fn typed_test_with_associated_items<T: Widen>(zero: DefaultFixture<T::Wide>) -> Result<()>
+    where
+        T::Wide: Default,
+    {
+        verify_that!(T::Wide::from(T::MAX), gt(zero.0))
+    }
+
+    struct ZeroFixture<T>(T);
+
+    impl<T: Zero> Fixture for ZeroFixture<T> {
+        fn set_up() -> Result<Self> {
+            Ok(Self(T::zero()))
+        }
+
+        fn tear_down(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[gtest(types = [u8])]
+    fn typed_test_with_qualified_path_and_lifetime<'a, T>(
+        zero: &'a ZeroFixture<<T as Widen>::Wide>,
+    ) -> Result<()>
+    where
+        T: Widen,
+        <T as Widen>::Wide: Zero + 'a,
+    {
+        verify_that!(T::Wide::from(T::MAX), gt(zero.0))
+    }
+
+    #[gtest]
+    fn typed_test_keeps_the_original_function() -> Result<()> {
+        typed_test_runs_for_each_type::<i8>()
+    }
+}
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use quote::quote;
+use quote::{format_ident, quote};
+use std::time::Duration;
+use syn::visit_mut::{self, VisitMut};
 use syn::{
-    parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute,
-    DeriveInput, Expr, ExprLit, FnArg, ItemFn, Lit, MetaNameValue, PatType, ReturnType, Signature,
-    Type,
+    parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma,
+    Attribute, DeriveInput, Expr, ExprArray, ExprAssign, ExprLit, ExprPath, FnArg, GenericArgument,
+    GenericParam, Generics, Ident, ItemFn, Lit, LitInt, LitStr, MetaNameValue, PatType, Path,
+    PathArguments, PredicateType, ReturnType, Signature, TraitBound, TraitBoundModifier, Type,
+    TypeParamBound, TypePath, Visibility, WherePredicate,
 };
 
 /// Marks a test to be run by the Google Rust test runner.
@@ -70,27 +74,603 @@ use syn::{
 /// }
 /// ```
 ///
+/// ## Value-parameterized tests
+///
+/// The argument `params` turns the test into one test case per listed value.
+/// The value is passed as the first argument of the test function. Any further
+/// arguments are fixtures, as usual:
+///
+/// ```ignore
+/// #[gtest(params = [1, 2, 3])]
+/// fn is_positive(value: u32, fixture: &MyFixture) -> googletest::Result<()> {
+///     verify_that!(value, gt(0))
+/// }
+/// ```
+///
+/// Each case is a separate test, named `is_positive::case_0`,
+/// `is_positive::case_1`, and so on, which can be filtered and sharded
+/// independently. A case can be given a label in place of its index:
+///
+/// ```ignore
+/// #[gtest(params = [small = 1, large = 1_000_000])]
+/// fn is_positive(value: u32) -> googletest::Result<()> {
+///     verify_that!(value, gt(0))
+/// }
+/// ```
+///
+/// This registers the tests `is_positive::small` and `is_positive::large`.
+///
+/// ## Typed tests
+///
+/// The argument `types` instantiates a generic test function once per listed
+/// type. The function must have exactly one type parameter:
+///
+/// ```ignore
+/// #[gtest(types = [u8, i32, f64])]
+/// fn zero_is_the_default<T: Default + num_traits::Zero + Debug>() -> googletest::Result<()> {
+///     verify_that!(T::default().is_zero(), eq(true))
+/// }
+/// ```
+///
+/// Each instantiation is a separate test named after its type, here
+/// `zero_is_the_default::u8`, `zero_is_the_default::i32` and
+/// `zero_is_the_default::f64`. Fixtures are supported as usual, and may depend
+/// on the type parameter. An associated type such as `T::Assoc` in the types
+/// of the fixtures or the return type requires `T` to have a single trait
+/// bound; otherwise, write it as `<T as Trait>::Assoc`.
+///
+/// ## Disabled tests
+///
+/// As in GoogleTest C++, a test whose name, or the name of one of its enclosing
+/// modules, starts with `DISABLED_` is not run and is reported as disabled:
+///
+/// ```ignore
+/// #[gtest]
+/// fn DISABLED_fails_until_bug_is_fixed() {
+///     ...
+/// }
+/// ```
+///
+/// Setting the environment variable `GTEST_ALSO_RUN_DISABLED_TESTS=1` runs
+/// disabled tests anyway.
+///
+/// ## Timeouts
+///
+/// The argument `timeout` fails the test if it does not complete within the
+/// given duration, written with the units `ms`, `s`, `m` or `h`:
+///
+/// ```ignore
+/// #[gtest(timeout = "5s")]
+/// fn completes_quickly() {
+///     ...
+/// }
+/// ```
+///
+/// The failure lists the `scoped_trace!` messages active at that point. The
+/// body of an `async` test is dropped once the timeout elapses, and its
+/// fixtures are then torn down as usual. The timeout does not include their set
+/// up and teardown. A synchronous
+/// test cannot be interrupted, so its body runs on a separate thread which is
+/// left behind if it does not complete in time. Its fixtures are set up on that
+/// thread as well, so they are only torn down if the thread completes later on.
+///
+/// ## Retries
+///
+/// The argument `retries` runs a failing test again, up to the given number of
+/// times, until it passes:
+///
+/// ```ignore
+/// #[gtest(retries = 2)]
+/// fn talks_to_a_flaky_service(service: &ServiceFixture) -> googletest::Result<()> {
+///     ...
+/// }
+/// ```
+///
+/// Each attempt starts from scratch: fixtures are set up again and failures of
+/// earlier attempts do not count against it. A test which passes only after a
+/// retry is reported as flaky on the standard error, together with the
+/// failures of the earlier attempts. The environment variable `GTEST_RETRIES`
+/// sets the number of retries of tests without the argument `retries`. A
+/// timeout applies to each attempt separately.
+///
+/// Tests using rstest and `#[should_panic]` tests are never retried.
+///
+/// ## Expected failures
+///
+/// The argument `expect_failure` marks a test which is known to fail, for
+/// instance because of an open bug, optionally with the reason:
+///
+/// ```ignore
+/// #[gtest(expect_failure = "https://github.com/org/repo/issues/123")]
+/// fn handles_unicode_names() {
+///     expect_that!(normalize("Ǆ"), eq("ǆ"));
+/// }
+/// ```
+///
+/// The test passes when it fails, through an assertion failure or a returned
+/// error, and is reported as an expected failure. It fails when it passes, so
+/// that the fix of the failure does not go unnoticed. Unlike
+/// `#[should_panic]`, this neither requires the failure to be a panic nor
+/// hides whether it still happens. Tests expected to fail are never retried.
+///
+/// A panic, such as an unwrapped `None` or an index out of bounds, still fails
+/// the test, so that a crash does not hide behind the known failure. The
+/// argument `allow_panic` counts panics as the expected failure as well:
+///
+/// ```ignore
+/// #[gtest(expect_failure = "https://github.com/org/repo/issues/456", allow_panic)]
+/// fn parses_empty_input() {
+///     parse("").unwrap();
+/// }
+/// ```
+///
+/// ## Custom test main
+///
+/// In a test target built with `harness = false`, the tests are run by the
+/// runner of `googletest::main!()` instead of the Rust test harness, except
+/// for tests with another test attribute, such as `#[tokio::test]`, and
+/// rstest tests.
+///
 /// [`googletest::Result`]: type.Result.html
 #[proc_macro_attribute]
 pub fn gtest(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let ItemFn { mut attrs, sig, block, .. } = parse_macro_input!(input as ItemFn);
+    let mut test_args = TestArgs::default();
+    let args_parser = syn::meta::parser(|meta| test_args.parse(meta));
+    parse_macro_input!(args with args_parser);
+    let item = parse_macro_input!(input as ItemFn);
 
-    let sig_ident = &sig.ident;
-    let test_case_hash: u64 = {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut h = DefaultHasher::new();
-
-        // Only consider attrs and name for stability. Changing the function body should
-        // not affect the test case distribution.
-        attrs.hash(&mut h);
-        sig_ident.hash(&mut h);
-        h.finish()
+    let TestArgs { params, types, options } = test_args;
+    let result = match (params, types) {
+        (Some(_), Some(_)) => Err(syn::Error::new(
+            item.sig.span(),
+            "`params` and `types` cannot be combined in the same test.",
+        )),
+        (Some(params), None) => expand_test_cases(
+            item,
+            params
+                .into_iter()
+                .enumerate()
+                .map(|(i, param)| TestCase::from_param(i, param))
+                .collect(),
+            &options,
+        ),
+        (None, Some(types)) => expand_test_cases(
+            item,
+            types.into_iter().enumerate().map(|(i, ty)| TestCase::from_type(i, ty)).collect(),
+            &options,
+        ),
+        (None, None) => expand_test(item, &options, &quote! { module_path!() }),
+    };
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The arguments of the attribute macro `#[gtest]`.
+#[derive(Default)]
+struct TestArgs {
+    params: Option<Vec<TestParam>>,
+    types: Option<Vec<Type>>,
+    options: TestOptions,
+}
+
+/// The arguments of `#[gtest]` which apply to each generated test.
+#[derive(Default)]
+struct TestOptions {
+    /// The duration after which the test fails.
+    timeout: Option<Duration>,
+    /// How many times the test is retried if it fails.
+    retries: Option<u32>,
+    /// Whether the test is expected to fail, with the optional reason.
+    expect_failure: Option<Option<LitStr>>,
+    /// Whether a panic counts as the expected failure.
+    allow_panic: bool,
+}
+
+impl TestArgs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("params") {
+            let params: ExprArray = meta.value()?.parse()?;
+            self.params = Some(params.elems.into_iter().map(TestParam::new).collect());
+            Ok(())
+        } else if meta.path.is_ident("types") {
+            let value = meta.value()?;
+            let content;
+            syn::bracketed!(content in value);
+            self.types =
+                Some(Punctuated::<Type, Comma>::parse_terminated(&content)?.into_iter().collect());
+            Ok(())
+        } else if meta.path.is_ident("retries") {
+            let retries: LitInt = meta.value()?.parse()?;
+            self.options.retries = Some(retries.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("timeout") {
+            let timeout: LitStr = meta.value()?.parse()?;
+            self.options.timeout = Some(parse_duration(&timeout)?);
+            Ok(())
+        } else if meta.path.is_ident("expect_failure") {
+            let reason =
+                if meta.input.peek(syn::Token![=]) { Some(meta.value()?.parse()?) } else { None };
+            self.options.expect_failure = Some(reason);
+            Ok(())
+        } else if meta.path.is_ident("allow_panic") {
+            self.options.allow_panic = true;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported gtest argument"))
+        }
+    }
+}
+
+/// Parses a duration such as `"500ms"`, `"5s"` or `"1m30s"`.
+fn parse_duration(lit: &LitStr) -> syn::Result<Duration> {
+    let error = || {
+        syn::Error::new(
+            lit.span(),
+            "invalid duration; expected a positive duration like \"500ms\", \"5s\", \"2m\" \
+             or \"1h\"",
+        )
+    };
+    let value = lit.value();
+    let mut rest = value.trim();
+    let mut duration = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let amount: u64 = rest[..digits_end].parse().map_err(|_| error())?;
+        rest = &rest[digits_end..];
+        let unit_end = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = match &rest[..unit_end] {
+            "ms" => Duration::from_millis(1),
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            _ => return Err(error()),
+        };
+        rest = &rest[unit_end..];
+        duration += unit * u32::try_from(amount).map_err(|_| error())?;
+    }
+    if duration.is_zero() {
+        return Err(error());
+    }
+    Ok(duration)
+}
+
+/// A single value of a value-parameterized test, with its optional label.
+struct TestParam {
+    label: Option<Ident>,
+    value: Expr,
+}
+
+impl TestParam {
+    fn new(expr: Expr) -> Self {
+        match expr {
+            Expr::Assign(ExprAssign { left, right, .. }) if is_label(&left) => {
+                let Expr::Path(ExprPath { path, .. }) = *left else { unreachable!() };
+                Self { label: path.get_ident().cloned(), value: *right }
+            }
+            value => Self { label: None, value },
+        }
+    }
+}
+
+/// Returns whether `expr` is a single identifier, which can label a test case.
+fn is_label(expr: &Expr) -> bool {
+    matches!(expr, Expr::Path(ExprPath { path, qself: None, .. }) if path.get_ident().is_some())
+}
+
+/// A test case of a value-parameterized or typed test.
+struct TestCase {
+    name: Ident,
+    /// The type argument of a typed test.
+    type_arg: Option<Type>,
+    /// The value of a value-parameterized test.
+    value: Option<Expr>,
+}
+
+impl TestCase {
+    fn from_param(index: usize, TestParam { label, value }: TestParam) -> Self {
+        let name = label.unwrap_or_else(|| format_ident!("case_{index}"));
+        Self { name, type_arg: None, value: Some(value) }
+    }
+
+    fn from_type(index: usize, type_arg: Type) -> Self {
+        let name = type_case_name(&type_arg).unwrap_or_else(|| format_ident!("type_{index}"));
+        Self { name, type_arg: Some(type_arg), value: None }
+    }
+}
+
+/// Derives the name of the test case for the type `ty`, for example `u8` for
+/// `u8` and `Vec_u8` for `Vec<u8>`.
+fn type_case_name(ty: &Type) -> Option<Ident> {
+    let name = quote!(#ty)
+        .to_string()
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    syn::parse_str::<Ident>(&name).ok()
+}
+
+/// Replaces a generic type parameter by a concrete type.
+///
+/// An associated type of the parameter such as `T::Assoc` becomes
+/// `<Replacement as Trait>::Assoc`, which requires `Trait` to be the only trait
+/// bound of the parameter. Otherwise, an error is recorded.
+struct ReplaceTypeParam<'a> {
+    param: &'a Ident,
+    replacement: &'a Type,
+    /// The only trait bound of the parameter, if it has exactly one.
+    bound: Option<&'a Path>,
+    error: Option<syn::Error>,
+}
+
+impl<'a> ReplaceTypeParam<'a> {
+    fn new(param: &'a Ident, replacement: &'a Type, bound: Option<&'a Path>) -> Self {
+        Self { param, replacement, bound, error: None }
+    }
+
+    /// Returns whether `path` is an associated item of the parameter, such as
+    /// `T::Assoc`.
+    fn is_associated_item(&self, path: &Path) -> bool {
+        path.leading_colon.is_none()
+            && path.segments.len() > 1
+            && path.segments[0].ident == *self.param
+            && path.segments[0].arguments.is_none()
+    }
+}
+
+impl VisitMut for ReplaceTypeParam<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident(self.param) => {
+                *ty = self.replacement.clone();
+            }
+            Type::Path(TypePath { qself: None, path }) if self.is_associated_item(path) => {
+                let Some(bound) = self.bound else {
+                    self.error.get_or_insert_with(|| {
+                        syn::Error::new(
+                            path.span(),
+                            format!(
+                                "Use a fully qualified path such as `<{} as Trait>::...` in the \
+                                 signature of a typed test whose type parameter does not have \
+                                 exactly one trait bound.",
+                                self.param
+                            ),
+                        )
+                    });
+                    return;
+                };
+                let replacement = self.replacement;
+                let associated = path.segments.iter().skip(1);
+                *ty = parse_quote! { <#replacement as #bound>#(::#associated)* };
+                // The trait and the associated item may have the parameter as
+                // generic argument.
+                visit_mut::visit_type_mut(self, ty);
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
+
+/// Returns the trait bound of the type parameter `param` declared in
+/// `generics`, if it has exactly one, without its associated item
+/// constraints, which a qualified path does not accept.
+fn single_trait_bound(generics: &Generics, param: &Ident) -> Option<Path> {
+    let param_bounds = generics
+        .type_params()
+        .filter(|type_param| type_param.ident == *param)
+        .flat_map(|type_param| &type_param.bounds);
+    let where_bounds = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Type(PredicateType {
+                bounded_ty: Type::Path(TypePath { qself: None, path }),
+                bounds,
+                ..
+            }) if path.is_ident(param) => Some(bounds),
+            _ => None,
+        })
+        .flatten();
+    let mut traits = param_bounds.chain(where_bounds).filter_map(|bound| match bound {
+        TypeParamBound::Trait(TraitBound { modifier: TraitBoundModifier::None, path, .. }) => {
+            Some(path)
+        }
+        _ => None,
+    });
+    let (Some(path), None) = (traits.next(), traits.next()) else {
+        return None;
+    };
+    let mut path = path.clone();
+    for segment in &mut path.segments {
+        if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+            arguments.args = arguments
+                .args
+                .iter()
+                .filter(|argument| {
+                    !matches!(
+                        argument,
+                        GenericArgument::AssocType(_)
+                            | GenericArgument::AssocConst(_)
+                            | GenericArgument::Constraint(_)
+                    )
+                })
+                .cloned()
+                .collect();
+            if arguments.args.is_empty() {
+                segment.arguments = PathArguments::None;
+            }
+        }
+    }
+    Some(path)
+}
+
+/// Replaces the type parameter `param` of a typed test by `replacement` in the
+/// signature `case_sig` of one of its cases, keeping its other generic
+/// parameters.
+fn replace_type_param(
+    case_sig: &mut Signature,
+    param: &Ident,
+    replacement: &Type,
+    bound: Option<&Path>,
+) -> syn::Result<()> {
+    case_sig.generics.params = std::mem::take(&mut case_sig.generics.params)
+        .into_iter()
+        .filter(|generic_param| {
+            !matches!(generic_param, GenericParam::Type(type_param) if type_param.ident == *param)
+        })
+        .collect();
+    if let Some(where_clause) = &mut case_sig.generics.where_clause {
+        // A bound which cannot be expressed for the concrete type is left out,
+        // as the call to the original function checks it anyway.
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter_map(|mut predicate| {
+                let mut replace = ReplaceTypeParam::new(param, replacement, bound);
+                replace.visit_where_predicate_mut(&mut predicate);
+                replace.error.is_none().then_some(predicate)
+            })
+            .collect();
+    }
+    let mut replace = ReplaceTypeParam::new(param, replacement, bound);
+    for input in case_sig.inputs.iter_mut() {
+        replace.visit_fn_arg_mut(input);
+    }
+    replace.visit_return_type_mut(&mut case_sig.output);
+    match replace.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Expands a value-parameterized or typed test into a module with one test per
+/// case.
+///
+/// The original function is kept as is, and each test case invokes it with its
+/// value or type argument and its fixtures.
+fn expand_test_cases(
+    item: ItemFn,
+    cases: Vec<TestCase>,
+    options: &TestOptions,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn { attrs, vis, sig, block } = item;
+    if is_rstest_enabled(&attrs) {
+        return Err(syn::Error::new(
+            sig.span(),
+            "`params` and `types` cannot be combined with rstest. Use either one to parameterize \
+             the test.",
+        ));
+    }
+    let takes_value = cases.iter().any(|case| case.value.is_some());
+    if takes_value && sig.inputs.is_empty() {
+        return Err(syn::Error::new(
+            sig.span(),
+            "A parameterized test must take the parameter as its first argument.",
+        ));
+    }
+    let type_param = if cases.iter().any(|case| case.type_arg.is_some()) {
+        let mut type_params = sig.generics.type_params();
+        match (type_params.next(), type_params.next()) {
+            (Some(type_param), None) => Some(&type_param.ident),
+            _ => {
+                return Err(syn::Error::new(
+                    sig.generics.span(),
+                    "A typed test must have exactly one type parameter.",
+                ))
+            }
+        }
+    } else {
+        None
     };
+    let bound = type_param.and_then(|param| single_trait_bound(&sig.generics, param));
+    let fixture_inputs = sig
+        .inputs
+        .iter()
+        .skip(usize::from(takes_value))
+        .enumerate()
+        .map(|(index, input)| {
+            let FnArg::Typed(PatType { ty, .. }) = input else {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`self` receiver is not accepted as test argument",
+                ));
+            };
+            Ok((format_ident!("__googletest_arg_{index}"), ty))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let fixture_idents = fixture_inputs.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+
+    let test_name = &sig.ident;
+    let maybe_await = sig.asyncness.is_some().then(|| quote! { .await });
+    let cases = cases
+        .into_iter()
+        .map(|TestCase { name, type_arg, value }| {
+            let fixture_args: Punctuated<FnArg, Comma> = fixture_inputs
+                .iter()
+                .map(|(ident, ty)| -> FnArg {
+                    parse_quote! { #ident: #ty }
+                })
+                .collect();
+            let mut case_sig = sig.clone();
+            case_sig.ident = name;
+            case_sig.inputs = fixture_args;
+            if let (Some(param), Some(replacement)) = (type_param, &type_arg) {
+                replace_type_param(&mut case_sig, param, replacement, bound.as_ref())?;
+            }
+            let turbofish = type_arg.map(|type_arg| quote! { ::<#type_arg> });
+            let args = value
+                .iter()
+                .map(|value| quote! { #value })
+                .chain(fixture_idents.iter().map(|ident| quote! { #ident }));
+            let case = ItemFn {
+                attrs: attrs.clone(),
+                vis: Visibility::Inherited,
+                sig: case_sig,
+                block: parse_quote! { {
+                    super::#test_name #turbofish (#(#args),*) #maybe_await
+                } },
+            };
+            // The case is defined in a module named after the test, within the
+            // module of the test.
+            expand_test(
+                case,
+                options,
+                &quote! { googletest::internal::test_report::parent_module(module_path!()) },
+            )
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
 
+    let allow_disabled_name = is_disabled(test_name).then(|| quote! { #[allow(non_snake_case)] });
+    Ok(quote! {
+        #allow_disabled_name
+        #vis #sig #block
+
+        #allow_disabled_name
+        mod #test_name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#cases)*
+        }
+    })
+}
+
+/// Expands a single test function into a test registered with the Rust test
+/// harness.
+///
+/// The expression `module` evaluates to the path of the module in which the
+/// test is defined.
+fn expand_test(
+    item: ItemFn,
+    options: &TestOptions,
+    module: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn { mut attrs, sig, block, .. } = item;
+
+    let sig_ident = &sig.ident;
+    let should_panic = attrs.iter().any(|attr| attr.path().is_ident("should_panic"));
     let (skipped_test_result, outer_return_type, trailer) = attrs
         .iter()
         .find(|attr| attr.path().is_ident("should_panic"))
@@ -113,7 +693,7 @@ pub fn gtest(
         ));
 
     let is_rstest_enabled = is_rstest_enabled(&attrs);
-    let outer_sig = {
+    let mut outer_sig = {
         let mut outer_sig = sig.clone();
         outer_sig.output = ReturnType::Default;
         if !is_rstest_enabled {
@@ -127,6 +707,10 @@ pub fn gtest(
         ReturnType::Type(_, ref ty) => Some(quote! {#ty}),
     };
     let is_async = sig.asyncness.is_some();
+    let timeout = options.timeout.map(|timeout| {
+        let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        quote! { ::core::time::Duration::from_millis(#millis) }
+    });
     let maybe_async = is_async.then(|| quote! { async });
     let maybe_await = is_async.then(|| quote! { .await });
     let invocation = if is_rstest_enabled {
@@ -143,10 +727,7 @@ pub fn gtest(
         }
         invocation
     } else {
-        let closure_body = match closure_body(&sig) {
-            Ok(body) => body,
-            Err(e) => return e.into_compile_error().into(),
-        };
+        let closure_body = closure_body(&sig, timeout.as_ref())?;
 
         // In the sync case, the ? operator returns from the surrounding function. So
         // we redeclare the original test function internally.
@@ -158,28 +739,226 @@ pub fn gtest(
             })() #maybe_await
         }
     };
-    if !attrs.iter().any(is_test_attribute) && !is_rstest_enabled {
+    // Runs a single attempt of the test with a fresh test outcome.
+    let attempt = match &timeout {
+        // The body of an async test is cancelled once the timeout elapses. The
+        // timeout is applied around the call of the test function, inside the
+        // set up and teardown of its fixtures, unless rstest sets up fixtures.
+        Some(timeout) if is_async && is_rstest_enabled => quote! {
+            TestOutcome::init_current_test_outcome();
+            TestOutcome::close_current_test_outcome(
+                match googletest::internal::test_timeout::with_timeout(
+                    #timeout, async move { #invocation }).await {
+                    ::core::option::Option::Some(result) => result,
+                    ::core::option::Option::None => ::core::result::Result::Ok(()),
+                })
+        },
+        // A sync test cannot be interrupted, so it runs on a thread which is
+        // abandoned if the timeout elapses. Its test outcome lives on that
+        // thread only.
+        Some(timeout) if !is_async => quote! {
+            googletest::internal::test_timeout::run_with_timeout(#timeout, move || {
+                TestOutcome::init_current_test_outcome();
+                TestOutcome::close_current_test_outcome(#invocation)
+            })
+        },
+        _ => quote! {
+            TestOutcome::init_current_test_outcome();
+            TestOutcome::close_current_test_outcome(#invocation)
+        },
+    };
+    if options.allow_panic && options.expect_failure.is_none() {
+        return Err(syn::Error::new(
+            sig.span(),
+            "`allow_panic` can only be combined with `expect_failure`.",
+        ));
+    }
+    if options.expect_failure.is_some() && should_panic {
+        return Err(syn::Error::new(
+            sig.span(),
+            "`expect_failure` cannot be combined with `#[should_panic]`.",
+        ));
+    }
+    // The arguments of an rstest test are consumed by the first attempt, and a
+    // `#[should_panic]` test or a test with `expect_failure` is expected to
+    // fail, so none of them is retried.
+    let is_retriable = !is_rstest_enabled && !should_panic && options.expect_failure.is_none();
+    if options.retries.is_some() && !is_retriable {
+        return Err(syn::Error::new(
+            sig.span(),
+            "`retries` cannot be combined with rstest, `#[should_panic]` or `expect_failure`.",
+        ));
+    }
+    let retries = match options.retries {
+        Some(retries) => quote! { ::core::option::Option::Some(#retries) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let attempts = if !is_retriable {
+        attempt
+    } else if is_async {
+        quote! {
+            googletest::internal::test_retry::run_with_retries_async(
+                __GOOGLETEST_TEST_NAME, #retries, || async { #attempt }).await
+        }
+    } else {
+        quote! {
+            googletest::internal::test_retry::run_with_retries(
+                __GOOGLETEST_TEST_NAME, #retries, || { #attempt })
+        }
+    };
+    let attempts = match &options.expect_failure {
+        None => attempts,
+        Some(reason) => {
+            let reason = match reason {
+                Some(reason) => quote! { ::core::option::Option::Some(#reason) },
+                None => quote! { ::core::option::Option::None },
+            };
+            // A panic is an expected failure only with `allow_panic`. Otherwise
+            // it propagates and fails the test.
+            let result = if !options.allow_panic {
+                quote! { ::core::result::Result::Ok({ #attempts }) }
+            } else if is_async {
+                quote! {
+                    googletest::__internal_macro_support::catch_unwind_async(
+                        async { #attempts }).await
+                }
+            } else {
+                quote! {
+                    ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| { #attempts }))
+                }
+            };
+            quote! {
+                googletest::internal::test_expected_failure::check_expected_failure(
+                    __GOOGLETEST_TEST_NAME, #reason, #result)
+            }
+        }
+    };
+    let run_test = quote! {
+        use googletest::internal::test_outcome::TestOutcome;
+        let __googletest_report_guard = googletest::internal::test_report::start_test(
+            __GOOGLETEST_TEST_NAME, #module, file!(), line!(), #should_panic);
+        // Every test fails if a global test environment failed to set up.
+        match googletest::environment::set_up_environments() {
+            ::core::result::Result::Ok(()) => { #attempts }
+            ::core::result::Result::Err(failure) => {
+                TestOutcome::init_current_test_outcome();
+                TestOutcome::close_current_test_outcome(::core::result::Result::Err(failure))
+            }
+        }
+    };
+    if is_disabled(sig_ident) {
+        attrs.push(parse_quote! { #[allow(non_snake_case)] });
+    }
+    let ignored = attrs.iter().any(|attr| attr.path().is_ident("ignore"));
+    // Under the Rust test harness, every test is registered to know the last
+    // test it runs. The cases of an rstest test are named by rstest, so they
+    // cannot be tracked.
+    let harness_registration = if is_rstest_enabled {
+        run_before_main(quote! {
+            googletest::internal::test_end::register_untracked_test();
+        })
+    } else {
+        run_before_main(quote! {
+            googletest::internal::test_end::register_harness_test(
+                googletest::internal::test_end::HarnessTest {
+                    name: concat!(module_path!(), "::", stringify!(#sig_ident)),
+                    ignored: #ignored,
+                    should_panic: #should_panic,
+                },
+            );
+        })
+    };
+    let has_test_attribute = attrs.iter().any(is_test_attribute) || is_rstest_enabled;
+    // With the feature `main`, the tests are kept outside of `cfg(test)` and
+    // registered to be run by `googletest::main!()`. A test with its own test
+    // attribute, such as `#[tokio::test]`, is only known to the Rust test
+    // harness, and `googletest::main!()` warns about it.
+    let main_registration = if !cfg!(feature = "main") {
+        if !has_test_attribute {
+            attrs.push(parse_quote! { #[::core::prelude::v1::test] });
+        }
+        None
+    } else if !has_test_attribute {
         let test_attr: Attribute = parse_quote! {
-            #[::core::prelude::v1::test]
+            #[cfg_attr(test, ::core::prelude::v1::test)]
         };
         attrs.push(test_attr);
+        let (should_panic, run) =
+            match attrs.iter().find(|attr| attr.path().is_ident("should_panic")) {
+                Some(attr) => {
+                    let expected = extract_should_panic_expected(attr).unwrap_or_default();
+                    (
+                        quote! { ::core::option::Option::Some(#expected) },
+                        quote! { || { #sig_ident(); ::core::result::Result::Ok(()) } },
+                    )
+                }
+                None => (quote! { ::core::option::Option::None }, quote! { #sig_ident }),
+            };
+        Some(run_before_main(quote! {
+            googletest::internal::test_main::register_test(
+                googletest::internal::test_main::RegisteredTest {
+                    name: concat!(module_path!(), "::", stringify!(#sig_ident)),
+                    module: #module,
+                    file: file!(),
+                    line: line!(),
+                    ignored: #ignored,
+                    should_panic: #should_panic,
+                    run: #run,
+                },
+            );
+        }))
+    } else {
+        Some(run_before_main(quote! {
+            googletest::internal::test_main::register_unsupported_test(
+                concat!(module_path!(), "::", stringify!(#sig_ident)),
+            );
+        }))
     };
+    // Only targets without the Rust test harness may use `googletest::main!()`.
+    let main_registration = main_registration.map(|registration| {
+        quote! {
+            #[cfg(not(test))]
+            #registration
+        }
+    });
+    let registration = quote! {
+        #[cfg(test)]
+        #harness_registration
+        #main_registration
+    };
+    // The where clause follows the return type, which is added below.
+    let where_clause = outer_sig.generics.where_clause.take();
     let function = quote! {
         #(#attrs)*
-        #outer_sig -> #outer_return_type {
-            if !googletest::internal::test_filter::test_should_run(concat!(module_path!(), "::", stringify!(#sig_ident))) {
+        #outer_sig -> #outer_return_type #where_clause {
+            const __GOOGLETEST_TEST_NAME: &str = concat!(module_path!(), "::", stringify!(#sig_ident));
+            let _end_guard = googletest::internal::test_end::TestEndGuard(__GOOGLETEST_TEST_NAME);
+            if !googletest::internal::test_filter::test_should_run(__GOOGLETEST_TEST_NAME) {
                 #skipped_test_result
-            } else if googletest::internal::test_sharding::test_should_run(#test_case_hash) {
-                use googletest::internal::test_outcome::TestOutcome;
-                TestOutcome::init_current_test_outcome();
-                TestOutcome::close_current_test_outcome(#invocation)
+            } else if googletest::internal::test_listing::list_test(
+                __GOOGLETEST_TEST_NAME, file!(), line!()) {
+                #skipped_test_result
+            } else if googletest::internal::test_filter::test_is_disabled(__GOOGLETEST_TEST_NAME) {
+                googletest::internal::test_report::record_disabled_test(
+                    __GOOGLETEST_TEST_NAME, file!(), line!());
+                #skipped_test_result
+            } else if googletest::internal::test_sharding::test_should_run(__GOOGLETEST_TEST_NAME) {
+                #run_test
             } else {
                 #skipped_test_result
             }
             #trailer
         }
+
+        #registration
     };
-    function.into()
+    Ok(function)
+}
+
+/// Returns whether the test `ident` is disabled by its name, which then does not
+/// follow the snake case convention.
+fn is_disabled(ident: &Ident) -> bool {
+    ident.to_string().starts_with("DISABLED_")
 }
 
 /// Extract the optional "expected" string literal from a `should_panic`
@@ -267,13 +1046,52 @@ impl Fixture {
         }
     }
 
-    fn wrap_call(&self, inner_call: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    /// Sets up the fixture of a synchronous test in the `FixtureScope`
+    /// `__googletest_fixtures`, binding its index there to the identifier.
+    fn set_up(&self) -> proc_macro2::TokenStream {
+        let Self { identifier, ty, kind } = self;
+        match kind {
+            FixtureKind::Consumable => quote! {
+                #[allow(non_snake_case)]
+                let #identifier =
+                    googletest::__internal_macro_support::set_up_consumable_fixture::<#ty>()?;
+            },
+            FixtureKind::MutableRef | FixtureKind::SharedRef => quote! {
+                #[allow(non_snake_case)]
+                let #identifier = __googletest_fixtures.set_up_argument::<#ty>()?;
+            },
+        }
+    }
+
+    /// Rebinds the identifier to the reference to the fixture set up by
+    /// [`Fixture::set_up`].
+    fn borrow(&self) -> proc_macro2::TokenStream {
+        let Self { identifier, ty, kind } = self;
+        let fixture = quote! { __googletest_fixture_arguments.take::<#ty>(#identifier) };
+        match kind {
+            FixtureKind::Consumable => quote! {},
+            FixtureKind::MutableRef => quote! {
+                #[allow(non_snake_case)]
+                let #identifier = #fixture;
+            },
+            FixtureKind::SharedRef => quote! {
+                #[allow(non_snake_case)]
+                let #identifier = &*#fixture;
+            },
+        }
+    }
+
+    /// Sets up the fixture of an `async` test around `inner_call`. Panics are
+    /// caught so that the fixture can be torn down asynchronously before
+    /// resuming them.
+    fn wrap_async_call(&self, inner_call: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
         let Self { identifier, ty, kind } = self;
         let ref_method = match kind {
             FixtureKind::Consumable => {
                 return quote! {
                     #[allow(non_snake_case)]
-                    let #identifier = <#ty as googletest::fixtures::ConsumableFixture>::set_up()?;
+                    let #identifier = googletest::__internal_macro_support::
+                        set_up_async_consumable_fixture::<#ty>().await?;
                     { #inner_call }
                 };
             }
@@ -283,20 +1101,28 @@ impl Fixture {
         quote! { {
             #[allow(non_snake_case, unused_mut)]
             let mut #identifier =
-                googletest::__internal_macro_support::FixtureTearDownOnDrop::new(
-                    <#ty as googletest::fixtures::Fixture>::set_up()?);
-            let result = {
+                googletest::__internal_macro_support::AsyncFixtureTearDownOnDrop::new(
+                    <#ty as googletest::fixtures::AsyncFixture>::set_up().await?);
+            let result = googletest::__internal_macro_support::catch_unwind_async(async {
                 #[allow(non_snake_case)]
                 let #identifier = #identifier #ref_method;
                 #inner_call
-            };
-            #identifier.tear_down()?;
+            }).await;
+            let tear_down_result = #identifier.tear_down().await;
+            let result = googletest::__internal_macro_support::resume_unwind_on_panic(result);
+            tear_down_result?;
             result
         } }
     }
 }
 
-fn closure_body(signature: &Signature) -> syn::Result<proc_macro2::TokenStream> {
+/// Returns the body of the closure invoking the test function `signature` with
+/// its fixtures. The call of an `async` test function is cancelled if it does
+/// not complete within `timeout`, after which its fixtures are still torn down.
+fn closure_body(
+    signature: &Signature,
+    timeout: Option<&proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
     let input_types = signature
         .inputs
         .iter()
@@ -328,13 +1154,152 @@ fn closure_body(signature: &Signature) -> syn::Result<proc_macro2::TokenStream>
         invocation
     };
 
-    for fixture in input_types.iter().rev() {
-        block = fixture.wrap_call(block);
+    if signature.asyncness.is_some() {
+        if let Some(timeout) = timeout {
+            block = quote! {
+                match googletest::internal::test_timeout::with_timeout(
+                    #timeout, async { #block }).await {
+                    ::core::option::Option::Some(result) => result,
+                    ::core::option::Option::None => ::core::result::Result::Ok(()),
+                }
+            };
+        }
+        for fixture in input_types.iter().rev() {
+            block = fixture.wrap_async_call(block);
+        }
+    } else if !input_types.is_empty() {
+        // The fixtures of a synchronous test may depend on each other, so they
+        // are all set up in one `FixtureScope`, which tears them down in the
+        // reverse order.
+        let set_ups = input_types.iter().map(Fixture::set_up);
+        let borrows = input_types.iter().map(Fixture::borrow);
+        block = quote! { {
+            #[allow(unused_mut)]
+            let mut __googletest_fixtures =
+                googletest::__internal_macro_support::FixtureScope::new();
+            #(#set_ups)*
+            let result = {
+                #[allow(unused_mut, unused_variables)]
+                let mut __googletest_fixture_arguments = __googletest_fixtures.arguments();
+                #(#borrows)*
+                #block
+            };
+            __googletest_fixtures.tear_down()?;
+            result
+        } };
     }
 
     Ok(block)
 }
 
+/// Registers a global test environment, set up before the first `#[gtest]`
+/// test of the test binary and torn down after the last one.
+///
+/// The annotated type must implement `googletest::environment::Environment`
+/// and `Default`:
+///
+/// ```ignore
+/// #[gtest_environment]
+/// #[derive(Default)]
+/// struct TestDatabase;
+///
+/// impl Environment for TestDatabase {
+///     fn set_up(&mut self) -> Result<()> {
+///         start_database()
+///     }
+/// }
+/// ```
+///
+/// The environment is registered before `main` runs, so it may be defined in
+/// any module of the test binary. See the module `googletest::environment`
+/// for when environments are set up and torn down.
+#[proc_macro_attribute]
+pub fn gtest_environment(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    parse_macro_input!(args as syn::parse::Nothing);
+    let item = parse_macro_input!(input as DeriveInput);
+    if !item.generics.params.is_empty() {
+        return syn::Error::new(
+            item.generics.span(),
+            "#[gtest_environment] cannot be applied to a generic type.",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let ident = &item.ident;
+    let register = run_before_main(quote! {
+        googletest::environment::register_environment::<#ident>();
+    });
+    let targets = constructor_section_targets();
+    quote! {
+        #item
+
+        #[cfg(not(#targets))]
+        compile_error!(
+            "#[gtest_environment] is not supported on this target, which cannot register the \
+            environment before `main`."
+        );
+        #register
+    }
+    .into()
+}
+
+/// The `cfg` predicate of the targets whose object file formats have a
+/// constructor section, which [`run_before_main`] relies on.
+fn constructor_section_targets() -> proc_macro2::TokenStream {
+    quote! {
+        any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_vendor = "apple",
+            windows,
+        )
+    }
+}
+
+/// Expands to an item running `body` before `main`, from the constructor
+/// section of the object file.
+///
+/// Nothing runs on the targets outside of [`constructor_section_targets`], so
+/// callers which cannot do without `body` must fail the compilation there.
+fn run_before_main(body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        const _: () = {
+            // SAFETY: The loader calls the functions of the constructor section
+            // before `main`, while the Rust runtime is not initialized: the
+            // command line arguments, the handle of the current thread and the
+            // standard streams are not set up yet, and a panic cannot unwind
+            // out of the `extern "C"` function. `body` therefore only pushes
+            // to a `static` which is initialized at compile time, and does not
+            // panic.
+            #[used]
+            #[cfg_attr(
+                any(
+                    target_os = "linux",
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd",
+                ),
+                unsafe(link_section = ".init_array")
+            )]
+            #[cfg_attr(target_vendor = "apple", unsafe(link_section = "__DATA,__mod_init_func"))]
+            #[cfg_attr(windows, unsafe(link_section = ".CRT$XCU"))]
+            static __GOOGLETEST_RUN_BEFORE_MAIN: extern "C" fn() = {
+                extern "C" fn run_before_main() {
+                    #body
+                }
+                run_before_main
+            };
+        };
+    }
+}
+
 #[proc_macro_derive(MatcherBase)]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
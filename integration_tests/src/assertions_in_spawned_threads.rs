@@ -0,0 +1,53 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use googletest::thread::TestContext;
+
+    #[gtest]
+    fn passing_assertions_in_scoped_threads() {
+        let values = [1, 2, 3, 4];
+        googletest::thread::scope(|s| {
+            for value in &values {
+                s.spawn(move || expect_that!(*value, gt(0)));
+            }
+        });
+    }
+
+    #[gtest]
+    fn failing_assertions_in_scoped_threads() {
+        scoped_trace!("checking workers");
+        googletest::thread::scope(|s| {
+            for worker in 0..4 {
+                s.spawn(move || expect_that!(worker, eq(-1)));
+            }
+        });
+    }
+
+    #[gtest]
+    fn failing_assertion_in_spawned_thread() {
+        googletest::thread::spawn(|| expect_that!("worker", eq("main"))).join().unwrap();
+    }
+
+    #[gtest]
+    fn failing_assertion_with_test_context() {
+        let context = TestContext::current().unwrap();
+        std::thread::spawn(move || context.run(|| expect_that!(1, eq(2)))).join().unwrap();
+    }
+}
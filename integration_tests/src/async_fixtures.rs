@@ -0,0 +1,115 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::time::Duration;
+    use tokio::task::JoinHandle;
+    use tokio::time::sleep;
+
+    struct BackgroundTask {
+        handle: JoinHandle<()>,
+        value: u32,
+    }
+
+    impl AsyncFixture for BackgroundTask {
+        async fn set_up() -> Result<Self> {
+            sleep(Duration::from_millis(1)).await;
+            let handle = tokio::spawn(sleep(Duration::from_secs(3600)));
+            Ok(Self { handle, value: 0 })
+        }
+
+        async fn tear_down(self) -> Result<()> {
+            self.handle.abort();
+            verify_that!(self.handle.await.unwrap_err().is_cancelled(), is_true())?;
+            println!("BackgroundTask torn down with value {}", self.value);
+            verify_that!(self.value, lt(10))
+        }
+    }
+
+    struct Pool(Vec<u32>);
+
+    impl AsyncConsumableFixture for Pool {
+        async fn set_up() -> Result<Self> {
+            sleep(Duration::from_millis(1)).await;
+            Ok(Self(vec![1, 2, 3]))
+        }
+    }
+
+    struct SyncFixture;
+
+    impl Fixture for SyncFixture {
+        fn set_up() -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn async_fixture_is_set_up(task: &BackgroundTask) -> Result<()> {
+        verify_that!(task.handle.is_finished(), is_false())
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn async_consumable_fixture_is_set_up(pool: Pool) -> Result<()> {
+        verify_that!(pool.0, elements_are![eq(&1), eq(&2), eq(&3)])
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn async_and_sync_fixtures_are_combined(
+        task: &mut BackgroundTask,
+        _: &SyncFixture,
+        pool: Pool,
+    ) -> Result<()> {
+        task.value = 1;
+        verify_that!(pool.0, len(eq(3)))
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn async_fixture_is_torn_down_after_failure(task: &mut BackgroundTask) -> Result<()> {
+        task.value = 2;
+        verify_that!(1, eq(2))
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn async_fixture_is_torn_down_after_panic(task: &mut BackgroundTask) {
+        task.value = 3;
+        panic!("Intentional panic");
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn failing_async_tear_down_fails_test(task: &mut BackgroundTask) {
+        task.value = 10;
+    }
+
+    #[gtest(timeout = "100ms")]
+    #[tokio::test]
+    async fn async_fixture_is_torn_down_after_timeout(task: &mut BackgroundTask) {
+        task.value = 4;
+        sleep(Duration::from_secs(60)).await;
+    }
+}
@@ -47,6 +47,17 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    #[tokio::test]
+    async fn async_test_failure_with_trace_in_spawned_task() -> Result<()> {
+        tokio::spawn(with_trace("checking the spawned task", async {
+            brief_sleep().await;
+            verify_that!(5, eq(6))
+        }))
+        .await
+        .unwrap()
+    }
+
     struct VerifyFiveOnTearDown(i32);
 
     impl Fixture for VerifyFiveOnTearDown {
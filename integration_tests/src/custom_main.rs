@@ -0,0 +1,78 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+googletest::main!();
+
+#[deny(warnings)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest_environment]
+    #[derive(Default)]
+    struct PrintingEnvironment;
+
+    impl Environment for PrintingEnvironment {
+        fn set_up(&mut self) -> Result<()> {
+            eprintln!("PrintingEnvironment set up");
+            Ok(())
+        }
+
+        fn tear_down(&mut self) -> Result<()> {
+            eprintln!("PrintingEnvironment torn down");
+            Ok(())
+        }
+    }
+
+    #[gtest]
+    fn passing_test() {
+        expect_that!(2 + 2, eq(4));
+    }
+
+    #[gtest]
+    fn failing_test() {
+        expect_that!(2 + 2, eq(5));
+    }
+
+    #[gtest]
+    fn skipped_test() -> Result<()> {
+        skip!("not applicable")
+    }
+
+    #[gtest]
+    #[should_panic(expected = "expected panic")]
+    fn panicking_test() {
+        panic!("Intentional expected panic");
+    }
+
+    #[gtest]
+    #[ignore]
+    fn ignored_test() {}
+
+    #[gtest]
+    fn DISABLED_disabled_test() {}
+
+    #[gtest(params = [1, 2])]
+    fn parameterized_test(value: i32) {
+        expect_that!(value, gt(0));
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn tokio_test() {}
+
+    #[gtest]
+    fn death_test() {
+        expect_exit!(std::process::exit(3), eq(3), anything());
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::io::Write as _;
+
+    fn exit_with_message(code: i32) {
+        eprintln!("exiting with code {code}");
+        std::process::exit(code);
+    }
+
+    #[gtest]
+    fn exit_with_expected_code_and_message() {
+        eprintln!("written before the statement");
+        expect_exit!(
+            exit_with_message(3),
+            eq(3),
+            all!(contains_substring("exiting with code 3"), not(contains_substring("before")))
+        );
+    }
+
+    #[gtest]
+    fn abort_is_death() {
+        expect_death!(
+            {
+                let _ = std::io::stderr().write_all(b"about to abort\n");
+                std::process::abort()
+            },
+            contains_substring("about to abort")
+        );
+    }
+
+    #[gtest]
+    fn panic_is_death() -> Result<()> {
+        verify_death!(panic!("Intentional panic in death test"), contains_substring("Intentional"))
+    }
+
+    #[gtest]
+    fn several_death_tests_in_one_test() {
+        expect_exit!(exit_with_message(1), eq(1), anything());
+        expect_exit!(exit_with_message(2), eq(2), anything());
+    }
+
+    #[gtest]
+    fn exit_with_unexpected_code() {
+        expect_exit!(exit_with_message(4), eq(5), anything());
+    }
+
+    #[gtest]
+    fn exit_with_unexpected_message() {
+        expect_exit!(exit_with_message(6), anything(), contains_substring("goodbye"));
+    }
+
+    #[gtest]
+    fn statement_returns() {
+        expect_death!(eprintln!("still alive"), anything());
+    }
+
+    #[gtest]
+    fn exit_with_success_is_not_death() {
+        expect_death!(exit_with_message(0), anything());
+    }
+}
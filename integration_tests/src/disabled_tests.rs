@@ -0,0 +1,38 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn passes() {}
+
+    #[gtest]
+    fn DISABLED_fails() {
+        expect_that!(1, eq(2));
+    }
+
+    #[allow(non_snake_case)]
+    mod DISABLED_suite {
+        use googletest::prelude::*;
+
+        #[gtest]
+        fn fails() {
+            expect_that!(1, eq(2));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest(expect_failure = "bug 123")]
+    fn non_fatal_failure_is_expected() {
+        expect_that!(1, eq(2));
+    }
+
+    #[gtest(expect_failure)]
+    fn fatal_failure_is_expected() -> Result<()> {
+        verify_that!(1, eq(2))
+    }
+
+    #[gtest(expect_failure, allow_panic)]
+    fn panic_is_expected() {
+        panic!("Intentional panic in test expected to fail");
+    }
+
+    #[gtest(expect_failure = "bug 1")]
+    fn panic_is_not_expected_by_default() {
+        panic!("Intentional unexpected panic in test expected to fail");
+    }
+
+    #[gtest(expect_failure = "bug 456")]
+    #[tokio::test]
+    async fn async_failure_is_expected() {
+        expect_that!(1, eq(2));
+    }
+
+    #[gtest(expect_failure = "bug 789")]
+    fn unexpectedly_passes() {
+        expect_that!(1, eq(1));
+    }
+
+    #[gtest(expect_failure)]
+    fn skipped_test_is_not_unexpected_pass() {
+        skip!("not applicable");
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::io::Write;
+
+    #[gtest_environment]
+    #[derive(Default)]
+    struct FailingEnvironment;
+
+    impl Environment for FailingEnvironment {
+        fn set_up(&mut self) -> Result<()> {
+            expect_that!(1, eq(2));
+            Ok(())
+        }
+
+        fn tear_down(&mut self) -> Result<()> {
+            writeln!(std::io::stderr(), "FailingEnvironment torn down").unwrap();
+            panic!("Intentional panic in tear down of FailingEnvironment");
+        }
+    }
+
+    #[gtest]
+    fn first_test() {}
+
+    #[gtest]
+    fn second_test() {}
+}
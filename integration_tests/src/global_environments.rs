@@ -0,0 +1,69 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static SET_UP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    #[gtest_environment]
+    #[derive(Default)]
+    struct FirstEnvironment;
+
+    impl Environment for FirstEnvironment {
+        fn set_up(&mut self) -> Result<()> {
+            SET_UP_COUNT.fetch_add(1, Ordering::SeqCst);
+            writeln!(std::io::stderr(), "FirstEnvironment set up").unwrap();
+            Ok(())
+        }
+
+        fn tear_down(&mut self) -> Result<()> {
+            writeln!(std::io::stderr(), "FirstEnvironment torn down").unwrap();
+            Ok(())
+        }
+    }
+
+    #[gtest_environment]
+    #[derive(Default)]
+    struct SecondEnvironment;
+
+    impl Environment for SecondEnvironment {
+        fn set_up(&mut self) -> Result<()> {
+            SET_UP_COUNT.fetch_add(1, Ordering::SeqCst);
+            writeln!(std::io::stderr(), "SecondEnvironment set up").unwrap();
+            Ok(())
+        }
+
+        fn tear_down(&mut self) -> Result<()> {
+            writeln!(std::io::stderr(), "SecondEnvironment torn down").unwrap();
+            Ok(())
+        }
+    }
+
+    #[gtest]
+    fn environments_are_set_up_before_first_test() {
+        expect_that!(SET_UP_COUNT.load(Ordering::SeqCst), eq(2));
+    }
+
+    #[gtest]
+    fn environments_are_set_up_only_once() {
+        expect_that!(SET_UP_COUNT.load(Ordering::SeqCst), eq(2));
+    }
+}
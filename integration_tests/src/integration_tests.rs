@@ -1868,86 +1868,1102 @@ mod tests {
         verify_that!(output, contains_substring("Expected: is equal to 4"))
     }
 
+    #[gtest]
+    fn async_test_failure_includes_trace_of_spawned_task() -> Result<()> {
+        let output = run_external_process_in_tests_directory("async_test_with_expect_that")?;
+
+        expect_that!(
+            output,
+            contains_substring("tests::async_test_failure_with_trace_in_spawned_task ... FAILED")
+        );
+        verify_that!(
+            output,
+            contains_regex(
+                "(?s)Expected: is equal to 6.*Google Test trace:.*checking the spawned task"
+            )
+        )
+    }
+
     #[gtest]
     fn test_can_return_anyhow_generated_error() -> Result<()> {
         let output = run_external_process_in_tests_directory("test_returning_anyhow_error")?;
 
-        verify_that!(output, contains_substring("Error from Anyhow"))
+        verify_that!(output, contains_substring("Error from Anyhow"))
+    }
+
+    #[gtest]
+    fn test_can_return_option_generated_error() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_returning_option")?;
+
+        verify_that!(
+            output,
+            all![
+                contains_substring("called `Option::or_fail()` on a `Option::<()>::None` value"),
+                contains_substring("test_returning_option.rs:23")
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_can_return_string_error_generated_error() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_returning_string_error")?;
+
+        verify_that!(
+            output,
+            all![
+                contains_substring("Error as a String"),
+                contains_substring("test_returning_string_error.rs:23")
+            ]
+        )
+    }
+
+    #[::core::prelude::v1::test]
+    #[should_panic]
+    fn should_panic_when_expect_that_runs_without_attribute_macro() {
+        expect_that!(123, eq(123));
+    }
+
+    #[::core::prelude::v1::test]
+    #[should_panic]
+    fn should_panic_when_and_log_failure_runs_without_attribute_macro() {
+        verify_that!(123, eq(123)).and_log_failure();
+    }
+
+    #[gtest]
+    fn should_just_pass() -> Result<()> {
+        Ok(())
+    }
+
+    #[gtest]
+    #[should_panic]
+    fn should_pass_with_should_panic() {
+        expect_that!(2, eq(4));
+    }
+
+    #[gtest]
+    #[should_panic(expected = "See failure output above")]
+    fn should_pass_with_should_panic_with_expectation() {
+        expect_that!(2, eq(4));
+    }
+
+    #[should_panic]
+    #[gtest]
+    fn should_pass_with_should_panic_in_first_position() {
+        expect_that!(2, eq(4));
+    }
+
+    #[gtest]
+    #[should_panic]
+    fn should_pass_with_should_panic_and_verify_that() -> Result<()> {
+        verify_that!(2, eq(4))?;
+        verify_that!(3, eq(3))
+    }
+
+    #[gtest]
+    fn should_fail_when_should_panic_is_present_and_no_panic_occurs() -> Result<()> {
+        let output = run_external_process_in_tests_directory("passing_test_with_should_panic")?;
+
+        verify_that!(output, contains_substring("should panic"))
+    }
+
+    #[gtest]
+    fn parameterized_test_runs_one_test_per_case() -> Result<()> {
+        let output = run_external_process_in_tests_directory("parameterized_test_with_failure")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::is_even::case_0 ... ok"),
+                contains_substring("test tests::is_even::case_1 ... FAILED"),
+                contains_substring("test tests::is_even::case_2 ... ok"),
+                contains_substring("test tests::is_positive::one ... ok"),
+                contains_substring("test tests::is_positive::minus_one ... FAILED"),
+                contains_substring("Value of: value % 2"),
+                contains_substring("test result: FAILED. 4 passed; 3 failed;")
+            )
+        )
+    }
+
+    #[gtest]
+    fn typed_test_runs_one_test_per_type() -> Result<()> {
+        let output = run_external_process_in_tests_directory("parameterized_test_with_failure")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::default_displays_as_empty::String ... ok"),
+                contains_substring("test tests::default_displays_as_empty::u8 ... FAILED"),
+                contains_substring("Value of: T::default().to_string()"),
+            )
+        )
+    }
+
+    #[gtest]
+    fn parameterized_test_cases_can_be_filtered_individually() -> Result<()> {
+        verify_that!(
+            execute_filtered_test(
+                "parameterized_test_with_failure",
+                "*::is_even::case_0,*::is_even::case_2,*::is_positive::one"
+            )?,
+            is_true()
+        )
+    }
+
+    #[gtest]
+    fn parameterized_test_cases_are_distributed_across_shards() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let mut reports = String::new();
+        for shard in 0..3 {
+            let report_path = report_dir.path().join(format!("shard_{shard}.json"));
+            run_external_process("parameterized_test_with_failure")
+                .env("GTEST_TOTAL_SHARDS", "3")
+                .env("GTEST_SHARD_INDEX", format!("{shard}"))
+                .env("GTEST_SHARD_STATUS_FILE", report_dir.path().join("shard_status_file"))
+                .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+                .output()?;
+            if let Ok(report) = std::fs::read_to_string(report_path) {
+                reports.push_str(&report);
+            }
+        }
+
+        verify_that!(
+            reports,
+            all!(
+                contains_substring(r#""test":"case_0""#).times(eq(1)),
+                contains_substring(r#""test":"case_1""#).times(eq(1)),
+                contains_substring(r#""test":"case_2""#).times(eq(1)),
+            )
+        )
+    }
+
+    #[gtest]
+    fn test_with_timeout_passes_when_completing_in_time() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_with_timeout")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::completes_within_timeout ... ok"),
+                contains_substring("test tests::fails_within_timeout ... FAILED"),
+                contains_substring("Expected: is equal to 2"),
+            )
+        )
+    }
+
+    #[gtest]
+    fn sync_test_fails_when_timeout_elapses() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_with_timeout")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::sync_test_times_out ... FAILED"),
+                contains_regex(indoc! {r#"
+                    Test did not complete within 100ms
+                    Google Test trace:
+                      .*test_with_timeout.rs:[0-9]+: Waiting in sync test
+                    "#}),
+                contains_substring("test result: FAILED. 2 passed; 3 failed;"),
+            )
+        )
+    }
+
+    #[gtest]
+    fn async_test_fails_when_timeout_elapses() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_with_timeout")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::async_test_times_out ... FAILED"),
+                contains_regex(indoc! {r#"
+                    Test did not complete within 100ms
+                    Google Test trace:
+                      .*test_with_timeout.rs:[0-9]+: Waiting in async test
+                    "#}),
+            )
+        )
+    }
+
+    #[gtest]
+    fn timed_out_test_is_reported_as_failed() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        run_external_process("test_with_timeout")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        let report = std::fs::read_to_string(report_path)?;
+        verify_that!(
+            report,
+            contains_regex(
+                r#""test":"sync_test_times_out",.*"status":"failed",.*"description":"Test did not complete within 100ms""#
+            )
+        )
+    }
+
+    #[gtest]
+    fn timed_out_attempt_does_not_fail_its_retry() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        let output = run_external_process("test_with_timeout")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        expect_that!(
+            String::from_utf8(output.stdout)?,
+            contains_substring("test tests::passes_on_retry_while_timed_out_attempt_fails ... ok")
+        );
+        let report = std::fs::read_to_string(report_path)?;
+        verify_that!(
+            report,
+            all!(
+                contains_regex(
+                    r#""test":"passes_on_retry_while_timed_out_attempt_fails",[^}]*"status":"passed""#
+                ),
+                not(contains_substring("Late failure of timed out attempt")),
+            )
+        )
+    }
+
+    #[gtest]
+    fn retried_test_passing_on_later_attempt_is_reported_as_flaky() -> Result<()> {
+        let output = run_external_process("test_with_retries").output()?;
+
+        expect_that!(
+            String::from_utf8(output.stdout)?,
+            all!(
+                contains_substring("test tests::passes_on_second_attempt ... ok"),
+                contains_substring("test tests::async_test_passes_on_third_attempt ... ok"),
+            )
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            all!(
+                contains_regex(indoc! {r#"
+                    FLAKY test_with_retries::tests::passes_on_second_attempt: passed on attempt 2 of 3
+                      Attempt 1 failed:
+                        Value of: attempt
+                        Expected: is equal to 2
+                        Actual: 1,
+                    "#}),
+                contains_substring(
+                    "FLAKY test_with_retries::tests::async_test_passes_on_third_attempt: passed \
+                     on attempt 3 of 3"
+                ),
+                not(contains_substring("FLAKY test_with_retries::tests::always_fails")),
+            )
+        )
+    }
+
+    #[gtest]
+    fn retried_test_failing_on_all_attempts_shows_all_failures() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_with_retries")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::always_fails ... FAILED"),
+                contains_regex(indoc! {r#"
+                    Actual: 1,
+                      which isn't equal to 0
+                      at .*test_with_retries.rs:[0-9]+:[0-9]+
+
+                    Attempt 1 of 2 failed. Retrying.
+                    Value of: attempt
+                    Expected: is equal to 0
+                    Actual: 2,
+                    "#}),
+                contains_substring("Failed in all 2 attempts."),
+            )
+        )
+    }
+
+    #[gtest]
+    fn gtest_retries_sets_retries_of_tests_without_argument() -> Result<()> {
+        let without_retries = run_external_process_in_tests_directory("test_with_retries")?;
+        let with_retries =
+            run_external_process("test_with_retries").env("GTEST_RETRIES", "1").output()?;
+
+        expect_that!(
+            without_retries,
+            contains_substring("test tests::fails_on_first_attempt ... FAILED")
+        );
+        verify_that!(
+            String::from_utf8(with_retries.stdout)?,
+            contains_substring("test tests::fails_on_first_attempt ... ok")
+        )
+    }
+
+    #[gtest]
+    fn skipped_test_is_announced_as_skipped() -> Result<()> {
+        let output = run_external_process("test_with_skip").output()?;
+
+        expect_that!(
+            String::from_utf8(output.stdout)?,
+            all!(
+                contains_substring("test tests::skips ... ok"),
+                contains_substring("test tests::skips_async_test ... ok"),
+                contains_substring("test tests::fails_before_skipping ... FAILED"),
+                contains_substring("test tests::skips_with_custom_error_type ... ok"),
+                contains_substring("test result: FAILED. 4 passed; 2 failed;"),
+            )
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            all!(
+                contains_substring(
+                    "SKIPPED test_with_skip::tests::skips: Hardware feature is missing"
+                ),
+                contains_substring(
+                    "SKIPPED test_with_skip::tests::skips_async_test: Skipped in async test"
+                ),
+                contains_substring(
+                    "SKIPPED test_with_skip::tests::skips_with_custom_error_type: Skipped with \
+                    custom error type"
+                ),
+                not(contains_substring("SKIPPED test_with_skip::tests::fails_before_skipping")),
+            )
+        )
+    }
+
+    #[gtest]
+    fn fatal_failure_after_skip_fails_test() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_with_skip")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("test tests::fails_after_skipping ... FAILED"),
+                contains_regex(
+                    "(?s)---- tests::fails_after_skipping stdout ----\nValue of: 1\nExpected: is equal to 2"
+                ),
+            )
+        )
+    }
+
+    #[gtest]
+    fn skipped_test_is_reported_as_skipped() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let xml_path = report_dir.path().join("report.xml");
+        let json_path = report_dir.path().join("report.json");
+
+        run_external_process("test_with_skip")
+            .env("GTEST_OUTPUT", format!("xml:{}", xml_path.display()))
+            .output()?;
+        run_external_process("test_with_skip")
+            .env("GTEST_OUTPUT", format!("json:{}", json_path.display()))
+            .output()?;
+
+        expect_that!(
+            std::fs::read_to_string(xml_path)?,
+            all!(
+                contains_substring(r#"tests="6" failures="2" skipped="3""#),
+                contains_regex(indoc! {r#"
+                    <testcase name="skips" [^>]* result="skipped" [^>]*>
+                          <skipped message="Hardware feature is missing" />"#}),
+            )
+        );
+        verify_that!(
+            std::fs::read_to_string(json_path)?,
+            all!(
+                contains_regex(
+                    r#""test":"skips","[^\n]*"status":"skipped","skip_reason":"Hardware feature is missing","#
+                ),
+                contains_regex(
+                    r#""test":"fails_before_skipping",[^\n]*"status":"failed","duration"#
+                ),
+            )
+        )
+    }
+
+    #[gtest]
+    fn disabled_tests_do_not_run() -> Result<()> {
+        let output = run_external_process_in_tests_directory("disabled_tests")?;
+
+        verify_that!(output, contains_substring("test result: ok. 3 passed; 0 failed;"))
+    }
+
+    #[gtest]
+    fn disabled_tests_are_announced_with_their_count() -> Result<()> {
+        let output = run_external_process("disabled_tests").output()?;
+
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            all!(
+                contains_substring("DISABLED disabled_tests::tests::DISABLED_fails\n"),
+                contains_substring("DISABLED disabled_tests::tests::DISABLED_suite::fails\n"),
+                ends_with("\n  YOU HAVE 2 DISABLED TESTS\n\n"),
+            )
+        )
+    }
+
+    #[gtest]
+    fn disabled_tests_run_with_gtest_also_run_disabled_tests() -> Result<()> {
+        let output = run_external_process("disabled_tests")
+            .env("GTEST_ALSO_RUN_DISABLED_TESTS", "1")
+            .output()?;
+
+        verify_that!(
+            String::from_utf8(output.stdout)?,
+            all!(
+                contains_substring("test tests::DISABLED_fails ... FAILED"),
+                contains_substring("test tests::DISABLED_suite::fails ... FAILED"),
+                contains_substring("test result: FAILED. 1 passed; 2 failed;"),
+            )
+        )
+    }
+
+    #[gtest]
+    fn disabled_tests_are_reported_as_disabled() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        run_external_process("disabled_tests")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        verify_that!(
+            std::fs::read_to_string(report_path)?,
+            all!(
+                contains_regex(r#""test":"DISABLED_fails",[^\n]*"status":"disabled""#),
+                contains_regex(
+                    r#""suite":"disabled_tests::tests::DISABLED_suite","test":"fails",[^\n]*"status":"disabled""#
+                ),
+                contains_regex(r#""test":"passes",[^\n]*"status":"passed""#),
+            )
+        )
+    }
+
+    #[gtest]
+    fn tests_run_on_shard_given_by_documented_hash() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        for shard in 0..3 {
+            let report_path = report_dir.path().join(format!("shard_{shard}.json"));
+            run_external_process("parameterized_test_with_failure")
+                .env("GTEST_TOTAL_SHARDS", "3")
+                .env("GTEST_SHARD_INDEX", format!("{shard}"))
+                .env("GTEST_SHARD_STATUS_FILE", report_dir.path().join("shard_status_file"))
+                .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+                .output()?;
+            let report = std::fs::read_to_string(report_path).unwrap_or_default();
+            for name in report.lines().filter_map(|line| line.split('"').nth(3)) {
+                expect_that!(
+                    googletest::internal::test_sharding::test_shard_hash(name) % 3,
+                    eq(shard),
+                    "test {name}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn gtest_list_tests_lists_tests_instead_of_running_them() -> Result<()> {
+        let output = run_external_process("parameterized_test_with_failure")
+            .env("GTEST_LIST_TESTS", "1")
+            .output()?;
+
+        expect_that!(output.status.success(), is_true());
+        expect_that!(String::from_utf8(output.stdout)?, not(contains_substring("Value of:")));
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            contains_regex(
+                "(?m)^parameterized_test_with_failure::tests::is_even::case_1 \
+                 integration_tests/src/parameterized_test_with_failure.rs:[0-9]+ shard=0$"
+            )
+        )
+    }
+
+    #[gtest]
+    fn gtest_list_tests_writes_json_with_shard_to_file() -> Result<()> {
+        let name = "parameterized_test_with_failure::tests::is_positive::minus_one";
+        let listing_dir = tempfile::tempdir()?;
+        let listing_path = listing_dir.path().join("tests.json");
+        run_external_process("parameterized_test_with_failure")
+            .env("GTEST_LIST_TESTS", format!("json:{}", listing_path.display()))
+            .env("GTEST_FILTER", "*is_positive*")
+            .env("GTEST_TOTAL_SHARDS", "3")
+            .env("GTEST_SHARD_INDEX", "0")
+            .env("GTEST_SHARD_STATUS_FILE", listing_dir.path().join("shard_status_file"))
+            .output()?;
+        let shard = googletest::internal::test_sharding::test_shard_hash(name) % 3;
+
+        verify_that!(
+            std::fs::read_to_string(listing_path)?,
+            all!(
+                contains_regex(format!(
+                    r#"(?m)^\{{"name":"{name}","file":"[^"]*parameterized_test_with_failure.rs","line":[0-9]+,"shard":{shard}\}}$"#
+                )),
+                contains_substring(r#""name":"#).times(eq(2)),
+            )
+        )
+    }
+
+    #[gtest]
+    fn async_fixtures_are_set_up_and_torn_down() -> Result<()> {
+        let output = run_external_process_in_tests_directory("async_fixtures")?;
+
+        expect_that!(output, contains_regex("async_fixture_is_set_up ... ok"));
+        expect_that!(output, contains_regex("async_consumable_fixture_is_set_up ... ok"));
+        expect_that!(output, contains_regex("async_and_sync_fixtures_are_combined ... ok"));
+        expect_that!(output, contains_regex("failing_async_tear_down_fails_test ... FAILED"));
+        verify_that!(output, contains_regex("Expected: is less than 10\nActual: 10"))
+    }
+
+    #[gtest]
+    fn async_fixture_is_torn_down_after_test_fails() -> Result<()> {
+        let output = run_external_process_in_tests_directory("async_fixtures")?;
+
+        expect_that!(output, contains_regex("async_fixture_is_torn_down_after_failure ... FAILED"));
+        verify_that!(output, contains_substring("BackgroundTask torn down with value 2"))
+    }
+
+    #[gtest]
+    fn async_fixture_is_torn_down_before_panic_is_resumed() -> Result<()> {
+        let output = run_external_process_in_tests_directory("async_fixtures")?;
+
+        expect_that!(output, contains_regex("async_fixture_is_torn_down_after_panic ... FAILED"));
+        expect_that!(output, contains_substring("Intentional panic"));
+        verify_that!(output, contains_substring("BackgroundTask torn down with value 3"))
+    }
+
+    #[gtest]
+    fn async_fixture_is_torn_down_after_timeout() -> Result<()> {
+        let output = run_external_process_in_tests_directory("async_fixtures")?;
+
+        expect_that!(output, contains_regex("async_fixture_is_torn_down_after_timeout ... FAILED"));
+        expect_that!(output, contains_substring("Test did not complete within 100ms"));
+        verify_that!(output, contains_substring("BackgroundTask torn down with value 4"))
+    }
+
+    #[gtest]
+    fn static_fixture_is_set_up_once_per_scope() -> Result<()> {
+        let output = run_external_process("static_fixtures").output()?;
+
+        expect_that!(output.status.success(), is_true());
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            all!(
+                contains_substring("Server 0 set up"),
+                contains_substring("Server 1 set up"),
+                contains_substring("Server 2 set up"),
+                not(contains_substring("Server 3")),
+            )
+        )
+    }
+
+    #[gtest]
+    fn static_fixture_is_torn_down_after_last_test() -> Result<()> {
+        let output = run_external_process("static_fixtures").output()?;
+
+        let stderr = String::from_utf8(output.stderr)?;
+        expect_that!(
+            stderr,
+            all!(
+                contains_substring("Server 0 torn down"),
+                contains_substring("Server 1 torn down"),
+                contains_substring("Server 2 torn down"),
+            )
+        );
+        verify_that!(
+            stderr,
+            contains_regex(
+                "(?s)Failed to tear down .*FailingTearDown.*Intentional tear down failure"
+            )
+        )
+    }
+
+    #[gtest]
+    fn static_fixture_is_torn_down_after_last_test_selected_by_test_harness() -> Result<()> {
+        let output = run_external_process("static_fixtures")
+            .args(["--test-threads=1", "second_module"])
+            .output()?;
+
+        verify_that!(String::from_utf8(output.stderr)?, eq("Server 0 set up\nServer 0 torn down\n"))
+    }
+
+    #[gtest]
+    fn static_fixture_is_torn_down_after_last_test_with_other_test_attribute() -> Result<()> {
+        let output = run_external_process("static_fixtures")
+            .args(["--test-threads", "1", "--skip=module"])
+            .output()?;
+
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            contains_regex("(?s)Server [0-9] used by async test\n.*Server [0-9] torn down")
+        )
+    }
+
+    #[gtest]
+    fn assertions_in_spawned_threads_are_recorded_against_test() -> Result<()> {
+        let output = run_external_process_in_tests_directory("assertions_in_spawned_threads")?;
+
+        expect_that!(output, contains_substring("passing_assertions_in_scoped_threads ... ok"));
+        expect_that!(output, contains_substring("failing_assertions_in_scoped_threads ... FAILED"));
+        expect_that!(output, contains_substring("failing_assertion_in_spawned_thread ... FAILED"));
+        verify_that!(output, contains_substring("failing_assertion_with_test_context ... FAILED"))
+    }
+
+    #[gtest]
+    fn assertions_in_spawned_threads_inherit_scoped_traces() -> Result<()> {
+        let output = run_external_process_in_tests_directory("assertions_in_spawned_threads")?;
+
+        verify_that!(
+            output,
+            all!(
+                contains_substring("Expected: is equal to -1").times(eq(4)),
+                contains_substring("checking workers").times(eq(4)),
+            )
+        )
+    }
+
+    #[gtest]
+    fn recorded_property_is_printed_in_test_output() -> Result<()> {
+        let output = run_external_process_in_tests_directory("test_report_output")?;
+
+        verify_that!(output, contains_substring("Property seed: 42"))
+    }
+
+    #[gtest]
+    fn death_tests_pass_when_statement_terminates_process_as_expected() -> Result<()> {
+        let output = run_external_process_in_tests_directory("death_tests")?;
+
+        expect_that!(output, contains_substring("exit_with_expected_code_and_message ... ok"));
+        expect_that!(output, contains_substring("abort_is_death ... ok"));
+        expect_that!(output, contains_substring("panic_is_death ... ok"));
+        verify_that!(output, contains_substring("several_death_tests_in_one_test ... ok"))
+    }
+
+    #[gtest]
+    fn death_test_with_unexpected_exit_code_fails() -> Result<()> {
+        let output = run_external_process_in_tests_directory("death_tests")?;
+
+        expect_that!(output, contains_substring("exit_with_unexpected_code ... FAILED"));
+        verify_that!(
+            output,
+            contains_substring(indoc! {"
+                Value of: exit code of death test `exit_with_message(4)`
+                Expected: is equal to 5
+                Actual: 4,
+                  which isn't equal to 5
+                "})
+        )
+    }
+
+    #[gtest]
+    fn death_test_with_unexpected_stderr_fails() -> Result<()> {
+        let output = run_external_process_in_tests_directory("death_tests")?;
+
+        expect_that!(output, contains_substring("exit_with_unexpected_message ... FAILED"));
+        verify_that!(
+            output,
+            contains_substring(indoc! {r#"
+                Value of: stderr of death test `exit_with_message(6)`
+                Expected: contains a substring "goodbye"
+                Actual: "exiting with code 6\n",
+                "#})
+        )
     }
 
     #[gtest]
-    fn test_can_return_option_generated_error() -> Result<()> {
-        let output = run_external_process_in_tests_directory("test_returning_option")?;
+    fn death_test_fails_when_statement_does_not_die() -> Result<()> {
+        let output = run_external_process_in_tests_directory("death_tests")?;
 
+        expect_that!(output, contains_substring("statement_returns ... FAILED"));
+        expect_that!(
+            output,
+            contains_substring(
+                "The statement `eprintln!(\"still alive\")` of the death test returned instead \
+                of terminating the process."
+            )
+        );
+        expect_that!(output, contains_substring("exit_with_success_is_not_death ... FAILED"));
         verify_that!(
             output,
-            all![
-                contains_substring("called `Option::or_fail()` on a `Option::<()>::None` value"),
-                contains_substring("test_returning_option.rs:23")
-            ]
+            contains_substring(
+                "The statement `exit_with_message(0)` of the death test was expected to die, but \
+                the process exited with code 0."
+            )
         )
     }
 
     #[gtest]
-    fn test_can_return_string_error_generated_error() -> Result<()> {
-        let output = run_external_process_in_tests_directory("test_returning_string_error")?;
+    fn test_event_listeners_receive_events_in_order() -> Result<()> {
+        let output =
+            run_external_process("test_event_listener").arg("--test-threads=1").output()?;
+        let stderr = String::from_utf8(output.stderr)?;
+
+        expect_that!(
+            stderr,
+            contains_substring(indoc! {"
+                first: end a_registers_listeners: Passed
+                second: end a_registers_listeners: Passed
+                first: start b_fails_with_fixture_and_trace
+                second: start b_fails_with_fixture_and_trace
+                first: set up Resource
+                second: set up Resource
+                first: trace checking b
+                second: trace checking b
+                first: failure in b_fails_with_fixture_and_trace: Value of: 1
+                second: failure in b_fails_with_fixture_and_trace: Value of: 1
+                first: tear down Resource
+                second: tear down Resource
+                first: end b_fails_with_fixture_and_trace: Failed
+                second: end b_fails_with_fixture_and_trace: Failed
+                first: start c_passes
+                second: start c_passes
+                first: end c_passes: Passed
+                second: end c_passes: Passed
+                first: start d_skips
+                second: start d_skips
+                "})
+        );
+        verify_that!(
+            stderr,
+            contains_substring("first: end d_skips: Skipped\nsecond: end d_skips: Skipped\n")
+        )
+    }
+
+    #[gtest]
+    fn reports_all_non_fatal_failures_by_default() -> Result<()> {
+        let output = run_external_process_in_tests_directory("many_non_fatal_failures")?;
+
+        verify_that!(output, contains_substring("Expected: is less than 0").times(eq(20)))
+    }
+
+    #[gtest]
+    fn gtest_max_failures_per_test_stops_test_at_limit() -> Result<()> {
+        let output = run_external_process("many_non_fatal_failures")
+            .env("GTEST_MAX_FAILURES_PER_TEST", "3")
+            .output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        expect_that!(output.status.success(), eq(false));
+        expect_that!(stdout, contains_substring("Expected: is less than 0").times(eq(6)));
+        expect_that!(stdout, not(contains_substring("element 3")));
+        verify_that!(
+            stdout,
+            contains_substring(
+                "Stopping the test after 3 failures as set by GTEST_MAX_FAILURES_PER_TEST."
+            )
+        )
+    }
+
+    #[gtest]
+    fn gtest_max_failures_per_test_reached_in_spawned_thread_stops_test() -> Result<()> {
+        let output = run_external_process("many_non_fatal_failures")
+            .args(["fails_for_each_element_in_spawned_thread"])
+            .env("GTEST_MAX_FAILURES_PER_TEST", "3")
+            .output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        expect_that!(stdout, not(contains_substring("element 3 in thread")));
+        verify_that!(
+            stdout,
+            contains_regex(
+                "thread 'tests::fails_for_each_element_in_spawned_thread'.* panicked at .*\n\
+                Stopping the test after 3 failures as set by GTEST_MAX_FAILURES_PER_TEST."
+            )
+        )
+    }
+
+    #[gtest]
+    fn gtest_break_on_failure_stops_process_at_first_failure() -> Result<()> {
+        let output = run_external_process("many_non_fatal_failures")
+            .env("GTEST_BREAK_ON_FAILURE", "1")
+            .output()?;
+        let stderr = String::from_utf8(output.stderr)?;
+
+        // The process is stopped by a signal rather than exiting.
+        expect_that!(output.status.code(), none());
+        expect_that!(stderr, contains_substring("element 0"));
+        expect_that!(stderr, not(contains_substring("element 1")));
+        verify_that!(
+            stderr,
+            contains_substring("Stopping at this failure as GTEST_BREAK_ON_FAILURE is set.")
+        )
+    }
+
+    #[gtest]
+    fn test_expected_to_fail_passes_when_it_fails() -> Result<()> {
+        let output = run_external_process("expected_failures").output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+        let stderr = String::from_utf8(output.stderr)?;
+
+        expect_that!(stdout, contains_regex("non_fatal_failure_is_expected ... (.|\n)*ok"));
+        expect_that!(stdout, contains_regex("fatal_failure_is_expected ... (.|\n)*ok"));
+        expect_that!(stdout, contains_regex("panic_is_expected ... (.|\n)*ok"));
+        expect_that!(stdout, contains_regex("async_failure_is_expected ... (.|\n)*ok"));
+        expect_that!(
+            stderr,
+            contains_substring(
+                "EXPECTED FAILURE expected_failures::tests::non_fatal_failure_is_expected: bug 123"
+            )
+        );
+        verify_that!(
+            stderr,
+            contains_substring("EXPECTED FAILURE expected_failures::tests::panic_is_expected\n")
+        )
+    }
+
+    #[gtest]
+    fn test_expected_to_fail_fails_when_it_passes() -> Result<()> {
+        let output = run_external_process_in_tests_directory("expected_failures")?;
 
+        expect_that!(output, contains_substring("unexpectedly_passes ... FAILED"));
+        expect_that!(output, contains_regex("skipped_test_is_not_unexpected_pass ... (.|\n)*ok"));
         verify_that!(
             output,
-            all![
-                contains_substring("Error as a String"),
-                contains_substring("test_returning_string_error.rs:23")
-            ]
+            contains_substring("Test unexpectedly passed but is expected to fail: bug 789")
         )
     }
 
-    #[::core::prelude::v1::test]
-    #[should_panic]
-    fn should_panic_when_expect_that_runs_without_attribute_macro() {
-        expect_that!(123, eq(123));
+    #[gtest]
+    fn test_expected_to_fail_fails_when_it_panics_without_allow_panic() -> Result<()> {
+        let output = run_external_process("expected_failures").output()?;
+
+        expect_that!(
+            String::from_utf8(output.stdout)?,
+            contains_substring("panic_is_not_expected_by_default ... FAILED")
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            not(contains_substring("EXPECTED FAILURE expected_failures::tests::panic_is_not"))
+        )
     }
 
-    #[::core::prelude::v1::test]
-    #[should_panic]
-    fn should_panic_when_and_log_failure_runs_without_attribute_macro() {
-        verify_that!(123, eq(123)).and_log_failure();
+    #[gtest]
+    fn expected_failure_is_reported_with_its_reason() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        run_external_process("expected_failures")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        let report = std::fs::read_to_string(report_path)?;
+        expect_that!(
+            report,
+            contains_regex(
+                r#""test":"non_fatal_failure_is_expected",.*"status":"expected_failure","skip_reason":"Expected failure: bug 123""#
+            )
+        );
+        verify_that!(report, contains_regex(r#""test":"unexpectedly_passes",.*"status":"failed""#))
     }
 
     #[gtest]
-    fn should_just_pass() -> Result<()> {
-        Ok(())
+    fn global_environments_are_set_up_once_before_tests_and_torn_down_after_last_test() -> Result<()>
+    {
+        let output = run_external_process("global_environments").output()?;
+        let stderr = String::from_utf8(output.stderr)?;
+
+        expect_that!(output.status.success(), is_true());
+        expect_that!(
+            stderr,
+            all!(
+                contains_substring("FirstEnvironment set up").times(eq(1)),
+                contains_substring("SecondEnvironment set up").times(eq(1)),
+            )
+        );
+        verify_that!(
+            stderr,
+            any!(
+                contains_regex("(?s)SecondEnvironment set up.*FirstEnvironment torn down.*SecondEnvironment torn down"),
+                contains_regex("(?s)FirstEnvironment set up.*SecondEnvironment torn down.*FirstEnvironment torn down"),
+            )
+        )
     }
 
     #[gtest]
-    #[should_panic]
-    fn should_pass_with_should_panic() {
-        expect_that!(2, eq(4));
+    fn failing_global_environment_fails_every_test() -> Result<()> {
+        let output = run_external_process("failing_global_environment").output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        expect_that!(output.status.success(), is_false());
+        expect_that!(stdout, contains_substring("first_test ... FAILED"));
+        expect_that!(stdout, contains_substring("second_test ... FAILED"));
+        expect_that!(
+            stdout,
+            contains_substring(
+                "Global test environment failing_global_environment::tests::FailingEnvironment \
+                failed to set up:\nRecorded non-fatal assertion failures."
+            )
+            .times(eq(2))
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            all!(
+                contains_substring("FailingEnvironment torn down"),
+                contains_substring(
+                    "Failed to tear down global test environment \
+                    failing_global_environment::tests::FailingEnvironment:\n\
+                    Panicked: Intentional panic in tear down of FailingEnvironment"
+                )
+            )
+        )
     }
 
     #[gtest]
-    #[should_panic(expected = "See failure output above")]
-    fn should_pass_with_should_panic_with_expectation() {
-        expect_that!(2, eq(4));
+    fn custom_main_runs_tests_with_gtest_output() -> Result<()> {
+        let output = run_external_process("custom_main").output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        expect_that!(output.status.success(), is_false());
+        expect_that!(
+            stdout,
+            all!(
+                contains_substring(indoc! {"
+                    WARNING: googletest::main!() does not run 1 test with another test attribute or rstest:
+                      custom_main::tests::tokio_test
+                    [==========] Running 7 tests."}),
+                contains_substring("[ RUN      ] custom_main::tests::passing_test\n"),
+                contains_regex(r"\[       OK \] custom_main::tests::passing_test \(\d+ ms\)"),
+                contains_regex(r"\[  FAILED  \] custom_main::tests::failing_test \(\d+ ms\)"),
+                contains_regex(r"\[  SKIPPED \] custom_main::tests::skipped_test \(\d+ ms\)"),
+                contains_regex(r"\[       OK \] custom_main::tests::panicking_test "),
+                contains_regex(r"\[       OK \] custom_main::tests::death_test "),
+                contains_substring("Expected: is equal to 5"),
+                not(contains_substring("disabled_test")),
+                not(contains_substring("ignored_test")),
+            )
+        );
+        expect_that!(
+            stdout,
+            contains_substring(indoc! {"
+                [  PASSED  ] 5 tests.
+                [  SKIPPED ] 1 test, listed below:
+                [  SKIPPED ] custom_main::tests::skipped_test
+                [  FAILED  ] 1 test, listed below:
+                [  FAILED  ] custom_main::tests::failing_test
+
+                 1 FAILED TEST
+                  YOU HAVE 2 DISABLED TESTS
+            "})
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            contains_regex("(?s)PrintingEnvironment set up.*PrintingEnvironment torn down")
+        )
     }
 
-    #[should_panic]
     #[gtest]
-    fn should_pass_with_should_panic_in_first_position() {
-        expect_that!(2, eq(4));
+    fn custom_main_filters_and_repeats_tests() -> Result<()> {
+        let output = run_external_process("custom_main")
+            .args(["--gtest_filter=*passing_test:tests.parameterized_test.*", "--gtest_repeat=3"])
+            .output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        expect_that!(output.status.success(), is_true());
+        expect_that!(
+            stdout,
+            all!(
+                contains_substring("[ RUN      ] custom_main::tests::passing_test").times(eq(3)),
+                contains_substring("[ RUN      ] custom_main::tests::parameterized_test::case_1")
+                    .times(eq(3)),
+                contains_substring("Repeating all tests (iteration 3) . . ."),
+                contains_substring("[  PASSED  ] 3 tests.").times(eq(3)),
+                not(contains_substring("failing_test")),
+            )
+        );
+        verify_that!(
+            String::from_utf8(output.stderr)?,
+            contains_substring("PrintingEnvironment set up").times(eq(1))
+        )
     }
 
     #[gtest]
-    #[should_panic]
-    fn should_pass_with_should_panic_and_verify_that() -> Result<()> {
-        verify_that!(2, eq(4))?;
-        verify_that!(3, eq(3))
+    fn custom_main_shuffles_tests_by_seed() -> Result<()> {
+        let run_order = |seed: &str| -> Result<Vec<String>> {
+            let output = run_external_process("custom_main")
+                .args(["--gtest_shuffle", seed, "--gtest_filter=-*death_test"])
+                .output()?;
+            Ok(String::from_utf8(output.stdout)?
+                .lines()
+                .filter_map(|line| line.strip_prefix("[ RUN      ] "))
+                .map(String::from)
+                .collect())
+        };
+
+        let first = run_order("--gtest_random_seed=42")?;
+        let second = run_order("--gtest_random_seed=42")?;
+        let third = run_order("--gtest_random_seed=43")?;
+
+        expect_that!(first, len(eq(6)));
+        expect_that!(first, eq(&second));
+        verify_that!(first, not(eq(&third)))
     }
 
     #[gtest]
-    fn should_fail_when_should_panic_is_present_and_no_panic_occurs() -> Result<()> {
-        let output = run_external_process_in_tests_directory("passing_test_with_should_panic")?;
+    fn custom_main_prints_seed_of_shuffle() -> Result<()> {
+        let output = run_external_process("custom_main")
+            .args(["--gtest_shuffle", "--gtest_random_seed=42", "--gtest_filter=*passing_test"])
+            .output()?;
 
-        verify_that!(output, contains_substring("should panic"))
+        verify_that!(
+            String::from_utf8(output.stdout)?,
+            contains_substring("Note: Randomizing tests' orders with a seed of 42 .")
+        )
+    }
+
+    #[gtest]
+    fn custom_main_lists_tests() -> Result<()> {
+        let output = run_external_process("custom_main")
+            .args(["--gtest_list_tests", "--gtest_filter=-*death_test"])
+            .output()?;
+
+        expect_that!(output.status.success(), is_true());
+        verify_that!(
+            String::from_utf8(output.stdout)?,
+            eq(indoc! {"
+                custom_main::tests.
+                  passing_test
+                  failing_test
+                  skipped_test
+                  panicking_test
+                  ignored_test
+                  DISABLED_disabled_test
+                  parameterized_test::case_0
+                  parameterized_test::case_1
+            "})
+        )
+    }
+
+    #[gtest]
+    fn custom_main_runs_disabled_and_ignored_tests_on_request() -> Result<()> {
+        let output = run_external_process("custom_main")
+            .args(["--gtest_also_run_disabled_tests", "--gtest_filter=*disabled*:*ignored*"])
+            .output()?;
+
+        expect_that!(output.status.success(), is_true());
+        verify_that!(
+            String::from_utf8(output.stdout)?,
+            all!(
+                contains_substring("[ RUN      ] custom_main::tests::ignored_test"),
+                contains_substring("[ RUN      ] custom_main::tests::DISABLED_disabled_test"),
+                contains_substring("[  PASSED  ] 2 tests."),
+            )
+        )
+    }
+
+    #[gtest]
+    fn custom_main_writes_report() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        run_external_process("custom_main")
+            .arg(format!("--gtest_output=json:{}", report_path.display()))
+            .output()?;
+
+        verify_that!(
+            std::fs::read_to_string(report_path)?,
+            all!(
+                contains_regex(r#""test":"passing_test",.*"status":"passed""#),
+                contains_regex(r#""test":"failing_test",.*"status":"failed""#),
+            )
+        )
     }
 
     #[gtest]
@@ -1983,6 +2999,34 @@ mod tests {
         verify_that!(execute_filtered_test("always_fails", "-*::this_always_fails")?, is_true())
     }
 
+    #[gtest]
+    fn should_fail_when_failing_test_matches_gtest_filter() -> Result<()> {
+        verify_that!(
+            execute_with_gtest_filter("always_fails", "other.*:tests.this_always_fails")?,
+            is_false()
+        )
+    }
+
+    #[gtest]
+    fn should_pass_when_failing_test_excluded_by_gtest_filter() -> Result<()> {
+        verify_that!(
+            execute_with_gtest_filter("always_fails", "*-other.*:tests.this_always_fails")?,
+            is_true()
+        )
+    }
+
+    #[gtest]
+    fn should_prefer_testbridge_test_only_over_gtest_filter() -> Result<()> {
+        verify_that!(
+            run_external_process("always_fails")
+                .env("TESTBRIDGE_TEST_ONLY", "filter_should_mismatch")
+                .env("GTEST_FILTER", "*")
+                .status()?
+                .success(),
+            is_true()
+        )
+    }
+
     #[::core::prelude::v1::test]
     #[should_panic]
     fn should_panic_when_expect_that_runs_without_attribute_macro_after_another_test() {
@@ -2123,6 +3167,140 @@ mod tests {
         Ok(success)
     }
 
+    #[gtest]
+    fn writes_xml_report_when_requested_through_gtest_output() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.xml");
+
+        run_external_process("test_report_output")
+            .env("GTEST_OUTPUT", format!("xml:{}", report_path.display()))
+            .output()?;
+
+        let report = std::fs::read_to_string(report_path)?;
+        expect_that!(
+            report,
+            contains_substring(
+                r#"<testsuites tests="5" failures="3" skipped="0" errors="0" time=""#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#"<testcase name="passes" classname="test_report_output::tests" file="[^"]*test_report_output.rs" line="[0-9]+" status="run" result="completed" time="[0-9.]+" />"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#"<testcase name="passes_with_should_panic" classname="test_report_output::tests" [^>]* />"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(indoc! {r#"
+                <testcase name="fails_with_non_fatal_failure" [^>]*>
+                      <failure message="[^"]*test_report_output.rs:[0-9]+&#x0A;Value of: 1&#x0A;Expected: is equal to 2&#x0A;[^"]*" type=""><!\[CDATA\[.*test_report_output.rs:[0-9]+
+                Value of: 1
+                Expected: is equal to 2
+            "#})
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#"<testcase name="fails_with_fatal_failure" [^>]*>\s*<properties>\s*<property name="seed" value="42" />\s*</properties>\s*<failure message="[^"]*Expected: is equal to 3"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#"<testcase name="fails_with_panic" [^>]*>\s*<failure message="[^"]*test_report_output.rs:[0-9]+&#x0A;panicked: Something went wrong""#
+            )
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn writes_xml_report_to_xml_output_file() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("bazel.xml");
+
+        run_external_process("test_report_output").env("XML_OUTPUT_FILE", &report_path).output()?;
+
+        verify_that!(std::fs::read_to_string(report_path)?, contains_substring("<testsuites"))
+    }
+
+    #[gtest]
+    fn writes_json_report_when_requested_through_gtest_output() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        run_external_process("test_report_output")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        let report = std::fs::read_to_string(report_path)?;
+        expect_that!(report.lines().count(), eq(5));
+        expect_that!(
+            report,
+            contains_regex(
+                r#"\{"name":"test_report_output::tests::passes","suite":"test_report_output::tests","test":"passes","file":"[^"]*test_report_output.rs","line":[0-9]+,"status":"passed","duration_seconds":[0-9.]+,"failures":\[\]\}"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#""test":"fails_with_non_fatal_failure",.*"status":"failed",.*"actual_expr":"1","actual":"1","matcher_description":"is equal to 2","match_explanation":"which isn't equal to 2","custom_message":null,"traces":\[\]"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#""test":"fails_with_panic",.*"message":"panicked: Something went wrong","description":null"#
+            )
+        );
+        expect_that!(
+            report,
+            contains_regex(
+                r#""test":"fails_with_fatal_failure",.*"status":"failed","properties":\{"seed":"42"\},"#
+            )
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn writes_report_after_each_test_when_last_test_is_unknown() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.json");
+
+        // The cases of rstest tests cannot be tracked.
+        run_external_process("google_test_with_rstest")
+            .env("GTEST_OUTPUT", format!("json:{}", report_path.display()))
+            .output()?;
+
+        expect_that!(std::fs::read_dir(report_dir.path())?.count(), eq(1));
+        verify_that!(std::fs::read_to_string(report_path)?.lines().count(), eq(10))
+    }
+
+    #[gtest]
+    fn xml_report_leaves_out_tests_excluded_by_filter() -> Result<()> {
+        let report_dir = tempfile::tempdir()?;
+        let report_path = report_dir.path().join("report.xml");
+
+        run_external_process("test_report_output")
+            .env("GTEST_OUTPUT", format!("xml:{}", report_path.display()))
+            .env("TESTBRIDGE_TEST_ONLY", "*::passes")
+            .output()?;
+
+        verify_that!(
+            std::fs::read_to_string(report_path)?,
+            all!(
+                contains_substring(r#"tests="1" failures="0" skipped="0""#),
+                contains_substring(r#"<testcase name="passes" "#),
+                not(contains_substring("fails_with_panic"))
+            )
+        )
+    }
+
     fn execute_filtered_test(
         process: &'static str,
         testbridge_test_only: &'static str,
@@ -2132,4 +3310,11 @@ mod tests {
             .status()?
             .success())
     }
+
+    fn execute_with_gtest_filter(
+        process: &'static str,
+        gtest_filter: &'static str,
+    ) -> Result<bool> {
+        Ok(run_external_process(process).env("GTEST_FILTER", gtest_filter).status()?.success())
+    }
 }
@@ -0,0 +1,39 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn fails_for_each_element() {
+        for value in 0..10 {
+            expect_that!(value, lt(0), "element {value}");
+        }
+    }
+
+    #[gtest]
+    fn fails_for_each_element_in_spawned_thread() {
+        let worker = googletest::thread::spawn(|| {
+            for value in 0..10 {
+                expect_that!(value, lt(0), "element {value} in thread");
+            }
+        });
+        // The test continues when the thread has stopped.
+        let _ = worker.join();
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    #[gtest(params = [2, 3, 4])]
+    fn is_even(value: u32) -> Result<()> {
+        verify_that!(value % 2, eq(0))
+    }
+
+    #[gtest(params = [one = 1, minus_one = -1])]
+    fn is_positive(value: i32) -> Result<()> {
+        verify_that!(value, gt(0))
+    }
+
+    #[gtest(types = [String, u8])]
+    fn default_displays_as_empty<T: Default + ToString>() -> Result<()> {
+        verify_that!(T::default().to_string(), eq(""))
+    }
+}
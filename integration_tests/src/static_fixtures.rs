@@ -0,0 +1,93 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::io::Write as _;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Writes `message` to the standard error, bypassing the output capture of
+    /// the test harness.
+    fn announce(message: String) {
+        std::io::stderr().write_all(format!("{message}\n").as_bytes()).unwrap();
+    }
+
+    struct Server(u32);
+
+    impl StaticFixture for Server {
+        fn set_up_once() -> Result<Self> {
+            static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+            let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+            announce(format!("Server {id} set up"));
+            Ok(Self(id))
+        }
+
+        fn tear_down_once(&self) -> Result<()> {
+            announce(format!("Server {} torn down", self.0));
+            Ok(())
+        }
+    }
+
+    struct FailingTearDown;
+
+    impl StaticFixture for FailingTearDown {
+        fn set_up_once() -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down_once(&self) -> Result<()> {
+            fail!("Intentional tear down failure")
+        }
+    }
+
+    #[gtest]
+    fn uses_process_wide_server(_: &&Server, _: &&FailingTearDown) {}
+
+    #[gtest]
+    fn uses_process_wide_server_again(_: &&Server) {}
+
+    #[gtest]
+    #[tokio::test]
+    async fn uses_process_wide_server_in_async_test(server: &&Server) {
+        announce(format!("Server {} used by async test", server.0));
+    }
+
+    mod first_module {
+        use super::Server;
+        use googletest::prelude::*;
+
+        #[gtest]
+        fn uses_module_server(server: &ModuleScoped<Server>) {
+            expect_that!(server.0, lt(3));
+        }
+
+        #[gtest(params = [1, 2])]
+        fn uses_module_server_in_cases(_: u32, _: &ModuleScoped<Server>) {}
+    }
+
+    mod second_module {
+        use super::Server;
+        use googletest::prelude::*;
+
+        #[gtest]
+        fn uses_module_server(_: &ModuleScoped<Server>) {}
+
+        #[gtest]
+        fn uses_module_server_again(_: &ModuleScoped<Server>) {}
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[deny(warnings)]
+#[cfg(test)]
+mod tests {
+    use googletest::internal::test_outcome::TestAssertionFailure;
+    use googletest::listener::{add_test_event_listener, Outcome, TestEventListener, TraceInfo};
+    use googletest::prelude::*;
+    use googletest::TestInfo;
+    use std::io::Write as _;
+    use std::time::Duration;
+
+    /// Prints the events on the standard error, bypassing the output capture
+    /// of the test harness.
+    struct PrintingListener(&'static str);
+
+    impl PrintingListener {
+        fn print(&self, event: String) {
+            let _ = std::io::stderr().write_all(format!("{}: {event}\n", self.0).as_bytes());
+        }
+    }
+
+    impl TestEventListener for PrintingListener {
+        fn on_test_start(&self, test: &TestInfo) {
+            self.print(format!("start {}", test.name()));
+        }
+
+        fn on_assertion_failure(&self, test: Option<&TestInfo>, failure: &TestAssertionFailure) {
+            let test = test.map(TestInfo::name).unwrap_or_default();
+            let description = failure.description.lines().next().unwrap_or_default();
+            self.print(format!("failure in {test}: {description}"));
+        }
+
+        fn on_trace_pushed(&self, trace: &TraceInfo) {
+            self.print(format!("trace {}", trace.message));
+        }
+
+        fn on_test_end(&self, test: &TestInfo, outcome: Outcome, _: Duration) {
+            self.print(format!("end {}: {outcome:?}", test.name()));
+        }
+
+        fn on_fixture_set_up(&self, fixture: &'static str) {
+            self.print(format!("set up {}", fixture.rsplit("::").next().unwrap()));
+        }
+
+        fn on_fixture_tear_down(&self, fixture: &'static str) {
+            self.print(format!("tear down {}", fixture.rsplit("::").next().unwrap()));
+        }
+    }
+
+    struct Resource;
+
+    impl Fixture for Resource {
+        fn set_up() -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn tear_down(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    // The tests are meant to run in the order of their names on a single
+    // thread.
+    #[gtest]
+    fn a_registers_listeners() {
+        add_test_event_listener(PrintingListener("first"));
+        add_test_event_listener(PrintingListener("second"));
+    }
+
+    #[gtest]
+    fn b_fails_with_fixture_and_trace(_: &Resource) {
+        scoped_trace!("checking b");
+        expect_that!(1, eq(2));
+    }
+
+    #[gtest]
+    fn c_passes() {}
+
+    #[gtest]
+    fn d_skips() {
+        skip!("not applicable");
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[gtest(retries = 2)]
+    fn passes_on_second_attempt() {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        expect_that!(attempt, eq(2));
+    }
+
+    #[gtest(retries = 1)]
+    fn always_fails() {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        expect_that!(attempt, eq(0));
+    }
+
+    #[gtest(retries = 2)]
+    #[tokio::test]
+    async fn async_test_passes_on_third_attempt() -> Result<()> {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        verify_that!(attempt, eq(3))
+    }
+
+    #[gtest]
+    fn fails_on_first_attempt() {
+        static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+        let attempt = ATTEMPTS.fetch_add(1, Ordering::Relaxed) + 1;
+        assert_eq!(attempt, 2);
+    }
+}
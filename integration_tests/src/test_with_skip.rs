@@ -0,0 +1,71 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::internal::test_outcome::TestAssertionFailure;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn skips() {
+        skip!("Hardware feature is missing");
+    }
+
+    #[gtest]
+    #[tokio::test]
+    async fn skips_async_test() -> Result<()> {
+        skip!("Skipped in async test");
+    }
+
+    #[gtest]
+    fn fails_before_skipping() {
+        expect_that!(1, eq(2));
+        skip!("Skipped after failure");
+    }
+
+    fn skip_in_helper() -> Result<()> {
+        skip!("Skipped in helper");
+    }
+
+    #[gtest]
+    fn fails_after_skipping() -> Result<()> {
+        let _ = skip_in_helper();
+        verify_that!(1, eq(2))
+    }
+
+    #[derive(Debug)]
+    struct CustomError(String);
+
+    impl From<TestAssertionFailure> for CustomError {
+        fn from(failure: TestAssertionFailure) -> Self {
+            Self(failure.to_string())
+        }
+    }
+
+    impl std::fmt::Display for CustomError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "CustomError: {}", self.0)
+        }
+    }
+
+    #[gtest]
+    fn skips_with_custom_error_type() -> std::result::Result<(), CustomError> {
+        skip!("Skipped with custom error type");
+    }
+
+    #[gtest]
+    fn passes() {}
+}
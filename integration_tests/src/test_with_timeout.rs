@@ -0,0 +1,62 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Barrier;
+    use std::time::Duration;
+
+    #[gtest(timeout = "10s")]
+    fn completes_within_timeout() {
+        expect_that!(1, eq(1));
+    }
+
+    #[gtest(timeout = "10s")]
+    fn fails_within_timeout() {
+        expect_that!(1, eq(2));
+    }
+
+    #[gtest(timeout = "100ms")]
+    fn sync_test_times_out() {
+        scoped_trace!("Waiting in sync test");
+        std::thread::sleep(Duration::from_secs(60));
+    }
+
+    #[gtest(timeout = "100ms")]
+    #[tokio::test]
+    async fn async_test_times_out() {
+        scoped_trace!("Waiting in async test");
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+
+    static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+    static RETRY_STARTED: Barrier = Barrier::new(2);
+    static LATE_FAILURE_ADDED: Barrier = Barrier::new(2);
+
+    #[gtest(timeout = "1s", retries = 1)]
+    fn passes_on_retry_while_timed_out_attempt_fails() {
+        let is_first_attempt = ATTEMPTS.fetch_add(1, Ordering::SeqCst) == 0;
+        // The first attempt times out waiting here for the retry to start, and
+        // then fails while the retry is running.
+        RETRY_STARTED.wait();
+        if is_first_attempt {
+            add_failure!("Late failure of timed out attempt");
+        }
+        LATE_FAILURE_ADDED.wait();
+    }
+}
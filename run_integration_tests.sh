@@ -105,6 +105,21 @@ INTEGRATION_TEST_BINARIES=(
   "expect_true_macro_on_false_condition_with_format_args"
   "assert_pred_macro_on_assertion_failure_with_format_args"
   "expect_pred_macro_on_assertion_failure_with_format_args"
+  "test_report_output"
+  "parameterized_test_with_failure"
+  "test_with_timeout"
+  "test_with_retries"
+  "test_with_skip"
+  "disabled_tests"
+  "async_fixtures"
+  "static_fixtures"
+  "assertions_in_spawned_threads"
+  "death_tests"
+  "test_event_listener"
+  "many_non_fatal_failures"
+  "expected_failures"
+  "global_environments"
+  "failing_global_environment"
 )
 
 cargo build